@@ -40,11 +40,11 @@ init! { init_rand_xoshiro256starstar_x2, Xoshiro256StarStarX2, from_rng }
 init! { init_rand_xoshiro256starstar_x4, Xoshiro256StarStarX4, from_rng }
 init! { init_rand_xoshiro256starstar_x8, Xoshiro256StarStarX8, from_rng }
 
-/*init! { init_block_xsm32_x2, Xsm32x2, blocks_from_rng }
+init! { init_block_xsm32_x2, Xsm32x2, blocks_from_rng }
 init! { init_rand_xsm32_x2, Xsm32x2, from_rng }
 init! { init_block_xsm32_x4, Xsm32x4, blocks_from_rng }
 init! { init_rand_xsm32_x4, Xsm32x4, from_rng }
 init! { init_block_xsm32_x8, Xsm32x8, blocks_from_rng }
 init! { init_rand_xsm32_x8, Xsm32x8, from_rng }
 init! { init_block_xsm32_x16, Xsm32x16, blocks_from_rng }
-init! { init_rand_xsm32_x16, Xsm32x16, from_rng }*/
+init! { init_rand_xsm32_x16, Xsm32x16, from_rng }
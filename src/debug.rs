@@ -0,0 +1,97 @@
+//! Lane-wise hex formatting for generator `Debug` impls.
+//!
+//! Every lane is zero-padded to its full width, lane 0 first, so states of the
+//! same generator line up when printed next to each other.
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+use std::fmt;
+
+use packed_simd::*;
+
+/// A field of generator state.
+pub trait HexLanes {
+    fn fmt_lanes(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+/// Formats a borrowed field with `HexLanes`.
+pub struct Hex<'a, T: 'a>(pub &'a T);
+
+impl<'a, T: HexLanes> fmt::Debug for Hex<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_lanes(f)
+    }
+}
+
+struct Lane<T>(T);
+
+macro_rules! impl_lane {
+    ($($scalar:ty: $width:expr,)+) => {
+        $(
+            impl fmt::Debug for Lane<$scalar> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write!(f, "{:#0width$x}", self.0, width = $width + 2)
+                }
+            }
+        )+
+    };
+}
+
+impl_lane! { u8: 2, u16: 4, u32: 8, u64: 16, }
+
+macro_rules! impl_hex_lanes_vector {
+    ($($vector:ident,)+) => {
+        $(
+            impl HexLanes for $vector {
+                fn fmt_lanes(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.debug_list()
+                        .entries((0..$vector::lanes()).map(|i| Lane(self.extract(i))))
+                        .finish()
+                }
+            }
+        )+
+    };
+}
+
+impl_hex_lanes_vector! {
+    u8x2,  u8x4,  u8x8,  u8x16,  u8x32,  u8x64,
+    u16x2, u16x4, u16x8, u16x16, u16x32,
+    u32x2, u32x4, u32x8, u32x16,
+    u64x2, u64x4, u64x8,
+}
+
+macro_rules! impl_hex_lanes_array {
+    ($($n:expr,)+) => {
+        $(
+            impl<T: HexLanes> HexLanes for [T; $n] {
+                fn fmt_lanes(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.debug_list().entries(self.iter().map(Hex)).finish()
+                }
+            }
+        )+
+    };
+}
+
+impl_hex_lanes_array! { 2, 3, 4, 8, 11, 16, }
+
+// flags and indices rather than state lanes
+impl HexLanes for bool {
+    fn fmt_lanes(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl HexLanes for u8 {
+    fn fmt_lanes(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl HexLanes for __m128i {
+    fn fmt_lanes(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        u64x2::from_bits(*self).fmt_lanes(f)
+    }
+}
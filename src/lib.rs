@@ -11,6 +11,10 @@
 
 extern crate packed_simd;
 extern crate rand;
+#[cfg(feature = "serde1")]
+extern crate serde;
+#[cfg(all(test, feature = "serde1"))]
+extern crate serde_json;
 
 #[cfg(target_arch = "x86")]
 use std::arch::x86::*;
@@ -22,17 +26,26 @@ use std::{mem, slice};
 
 #[macro_use]
 mod utils;
+mod debug;
 mod prngs;
 mod rotate_opts;
+mod seed;
+#[cfg(feature = "serde1")]
+mod serde_impls;
 mod simd_rng;
+mod split;
 pub use prngs::*;
+pub use seed::*;
 pub use simd_rng::*;
+pub use split::*;
 
 mod rng_impl {
     pub use packed_simd::*;
     pub use rand::{Error, Rng, RngCore, SeedableRng};
     pub use rotate_opts::RotateOpt;
+    pub use seed::*;
     pub use simd_rng::*;
+    pub use split::*;
     pub use AsByteSliceMut;
 }
 
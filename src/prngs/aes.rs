@@ -0,0 +1,320 @@
+//! AES round functions on `u64x2`, with the same semantics as the AES-NI
+//! instructions.
+//!
+//! With AES-NI enabled at compile time (`-C target-feature=+aes`, or a
+//! `target-cpu` that has it) these are the instructions themselves. Otherwise
+//! they're computed in software: a bitsliced S-box circuit over the bits of
+//! every byte at once, with no tables and no branches on the data, so the
+//! fallback is constant-time like the hardware.
+//!
+//! The choice is made at compile time only, a build without `+aes` uses the
+//! software rounds even on a CPU with AES-NI.
+
+use rng_impl::*;
+
+/// AES-128's round constants, one per round of the key expansion.
+const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// The 11 round keys of AES-128, with the key expansion from FIPS-197.
+pub fn expand_key(key: [u8; 16]) -> [u64x2; 11] {
+    let mut keys = [u64x2::splat(0); 11];
+    keys[0] = u64x2::from_bits(u8x16::from_slice_unaligned(&key));
+
+    for round in 0..RCON.len() {
+        let prev = u32x4::from_bits(keys[round]);
+
+        // `keygen_assist` leaves the rotated, substituted and rcon'd last
+        // word in the top lane, each word of the next key xors in all words
+        // before it
+        let assist = u32x4::from_bits(keygen_assist(keys[round], round));
+        let assist: u32x4 = shuffle!(assist, [3, 3, 3, 3]);
+        let mut key = prev;
+        for _ in 0..3 {
+            key ^= shuffle!(u32x4::splat(0), key, [0, 4, 5, 6]);
+        }
+        keys[round + 1] = u64x2::from_bits(key ^ assist);
+    }
+    keys
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "aes"))]
+pub use self::ni::*;
+#[cfg(not(all(target_arch = "x86_64", target_feature = "aes")))]
+pub use self::soft::*;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "aes"))]
+mod ni {
+    use std::arch::x86_64::*;
+
+    use rng_impl::*;
+
+    macro_rules! wrap {
+        ($($name:ident = $intrinsic:ident;)+) => {
+            $(
+                #[inline(always)]
+                pub fn $name(x: u64x2, k: u64x2) -> u64x2 {
+                    let r = unsafe { $intrinsic(__m128i::from_bits(x), __m128i::from_bits(k)) };
+                    u64x2::from_bits(r)
+                }
+            )+
+        };
+    }
+
+    wrap! {
+        enc = _mm_aesenc_si128;
+        enc_last = _mm_aesenclast_si128;
+        dec = _mm_aesdec_si128;
+        dec_last = _mm_aesdeclast_si128;
+    }
+
+    #[inline(always)]
+    pub fn inv_mix_columns(x: u64x2) -> u64x2 {
+        u64x2::from_bits(unsafe { _mm_aesimc_si128(__m128i::from_bits(x)) })
+    }
+
+    /// `aeskeygenassist` with the constant of key expansion `round`, see
+    /// `RCON`.
+    #[inline(always)]
+    pub(super) fn keygen_assist(x: u64x2, round: usize) -> u64x2 {
+        let x = __m128i::from_bits(x);
+
+        // the round constant must be an immediate
+        macro_rules! assist {
+            ($($round:tt: $rcon:tt,)+) => {
+                match round {
+                    $($round => unsafe { _mm_aeskeygenassist_si128(x, $rcon) },)+
+                    _ => unreachable!("AES-128 has 10 key expansion rounds"),
+                }
+            };
+        }
+
+        u64x2::from_bits(assist! {
+            0: 0x01, 1: 0x02, 2: 0x04, 3: 0x08, 4: 0x10,
+            5: 0x20, 6: 0x40, 7: 0x80, 8: 0x1b, 9: 0x36,
+        })
+    }
+}
+
+#[cfg(any(test, not(all(target_arch = "x86_64", target_feature = "aes"))))]
+mod soft {
+    use rng_impl::*;
+
+    /// Multiplies every byte by `x` in GF(2^8), reducing without branches.
+    #[inline(always)]
+    fn xtime(x: u8x16) -> u8x16 {
+        (x << 1) ^ ((x >> 7) * 0x1b)
+    }
+
+    /// The AES S-box on every byte, as the 113-gate circuit of Boyar and
+    /// Peralta: each `x*` and `s*` is one bit of every byte, as a mask.
+    ///
+    /// - Source: Boyar and Peralta, "A new combinational logic minimization
+    ///   technique with applications to cryptology" (2010)
+    #[inline(always)]
+    #[rustfmt::skip]
+    fn sub_bytes(x: u8x16) -> u8x16 {
+        let bit = |i: u32| ((x >> i) & 1) * 0xff;
+        let (x0, x1, x2, x3) = (bit(7), bit(6), bit(5), bit(4));
+        let (x4, x5, x6, x7) = (bit(3), bit(2), bit(1), bit(0));
+
+        // top linear transformation
+        let y14 = x3 ^ x5; let y13 = x0 ^ x6; let y9 = x0 ^ x3; let y8 = x0 ^ x5;
+        let t0 = x1 ^ x2; let y1 = t0 ^ x7; let y4 = y1 ^ x3; let y12 = y13 ^ y14;
+        let y2 = y1 ^ x0; let y5 = y1 ^ x6; let y3 = y5 ^ y8; let t1 = x4 ^ y12;
+        let y15 = t1 ^ x5; let y20 = t1 ^ x1; let y6 = y15 ^ x7; let y10 = y15 ^ t0;
+        let y11 = y20 ^ y9; let y7 = x7 ^ y11; let y17 = y10 ^ y11; let y19 = y10 ^ y8;
+        let y16 = t0 ^ y11; let y21 = y13 ^ y16; let y18 = x0 ^ y16;
+
+        // nonlinear middle, the inversion in GF(2^8)
+        let t2 = y12 & y15; let t3 = y3 & y6; let t4 = t3 ^ t2; let t5 = y4 & x7;
+        let t6 = t5 ^ t2; let t7 = y13 & y16; let t8 = y5 & y1; let t9 = t8 ^ t7;
+        let t10 = y2 & y7; let t11 = t10 ^ t7; let t12 = y9 & y11; let t13 = y14 & y17;
+        let t14 = t13 ^ t12; let t15 = y8 & y10; let t16 = t15 ^ t12; let t17 = t4 ^ t14;
+        let t18 = t6 ^ t16; let t19 = t9 ^ t14; let t20 = t11 ^ t16; let t21 = t17 ^ y20;
+        let t22 = t18 ^ y19; let t23 = t19 ^ y21; let t24 = t20 ^ y18;
+
+        let t25 = t21 ^ t22; let t26 = t21 & t23; let t27 = t24 ^ t26; let t28 = t25 & t27;
+        let t29 = t28 ^ t22; let t30 = t23 ^ t24; let t31 = t22 ^ t26; let t32 = t31 & t30;
+        let t33 = t32 ^ t24; let t34 = t23 ^ t33; let t35 = t27 ^ t33; let t36 = t24 & t35;
+        let t37 = t36 ^ t34; let t38 = t27 ^ t36; let t39 = t29 & t38; let t40 = t25 ^ t39;
+
+        let t41 = t40 ^ t37; let t42 = t29 ^ t33; let t43 = t29 ^ t40; let t44 = t33 ^ t37;
+        let t45 = t42 ^ t41;
+        let z0 = t44 & y15; let z1 = t37 & y6; let z2 = t33 & x7; let z3 = t43 & y16;
+        let z4 = t40 & y1; let z5 = t29 & y7; let z6 = t42 & y11; let z7 = t45 & y17;
+        let z8 = t41 & y10; let z9 = t44 & y12; let z10 = t37 & y3; let z11 = t33 & y4;
+        let z12 = t43 & y13; let z13 = t40 & y5; let z14 = t29 & y2; let z15 = t42 & y9;
+        let z16 = t45 & y14; let z17 = t41 & y8;
+
+        // bottom linear transformation
+        let t46 = z15 ^ z16; let t47 = z10 ^ z11; let t48 = z5 ^ z13; let t49 = z9 ^ z10;
+        let t50 = z2 ^ z12; let t51 = z2 ^ z5; let t52 = z7 ^ z8; let t53 = z0 ^ z3;
+        let t54 = z6 ^ z7; let t55 = z16 ^ z17; let t56 = z12 ^ t48; let t57 = t50 ^ t53;
+        let t58 = z4 ^ t46; let t59 = z3 ^ t54; let t60 = t46 ^ t57; let t61 = z14 ^ t57;
+        let t62 = t52 ^ t58; let t63 = t49 ^ t58; let t64 = z4 ^ t59; let t65 = t61 ^ t62;
+        let t66 = z1 ^ t63;
+        let s0 = t59 ^ t63; let s6 = t56 ^ !t62; let s7 = t48 ^ !t60; let t67 = t64 ^ t65;
+        let s3 = t53 ^ t66; let s4 = t51 ^ t66; let s5 = t47 ^ t65; let s1 = t64 ^ !s3;
+        let s2 = t55 ^ !t67;
+
+        (s0 & 0x80) | (s1 & 0x40) | (s2 & 0x20) | (s3 & 0x10)
+            | (s4 & 0x08) | (s5 & 0x04) | (s6 & 0x02) | (s7 & 0x01)
+    }
+
+    /// The inverse S-box, the S-box between two copies of the inverse affine
+    /// map.
+    #[inline(always)]
+    fn inv_sub_bytes(x: u8x16) -> u8x16 {
+        let affine = |x: u8x16| {
+            x.rotate_left(u8x16::splat(1))
+                ^ x.rotate_left(u8x16::splat(3))
+                ^ x.rotate_left(u8x16::splat(6))
+                ^ 0x05
+        };
+        affine(sub_bytes(affine(x)))
+    }
+
+    #[inline(always)]
+    fn shift_rows(x: u8x16) -> u8x16 {
+        shuffle!(x, [0, 5, 10, 15, 4, 9, 14, 3, 8, 13, 2, 7, 12, 1, 6, 11])
+    }
+
+    #[inline(always)]
+    fn inv_shift_rows(x: u8x16) -> u8x16 {
+        shuffle!(x, [0, 13, 10, 7, 4, 1, 14, 11, 8, 5, 2, 15, 12, 9, 6, 3])
+    }
+
+    #[inline(always)]
+    fn mix(x: u8x16) -> u8x16 {
+        // each column's bytes rotated by 1, 2 and 3
+        let r1: u8x16 = shuffle!(x, [1, 2, 3, 0, 5, 6, 7, 4, 9, 10, 11, 8, 13, 14, 15, 12]);
+        let r2: u8x16 = shuffle!(x, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13]);
+        let r3: u8x16 = shuffle!(x, [3, 0, 1, 2, 7, 4, 5, 6, 11, 8, 9, 10, 15, 12, 13, 14]);
+        xtime(x ^ r1) ^ r1 ^ r2 ^ r3
+    }
+
+    #[inline(always)]
+    fn inv_mix(x: u8x16) -> u8x16 {
+        // multiplying by `{04}x^2 + {05}` first turns `mix` into its inverse
+        let r2: u8x16 = shuffle!(x, [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13]);
+        mix(x ^ xtime(xtime(x ^ r2)))
+    }
+
+    #[inline(always)]
+    pub fn enc(x: u64x2, k: u64x2) -> u64x2 {
+        let x = u8x16::from_bits(x);
+        u64x2::from_bits(mix(shift_rows(sub_bytes(x)))) ^ k
+    }
+
+    #[inline(always)]
+    pub fn enc_last(x: u64x2, k: u64x2) -> u64x2 {
+        let x = u8x16::from_bits(x);
+        u64x2::from_bits(shift_rows(sub_bytes(x))) ^ k
+    }
+
+    #[inline(always)]
+    pub fn dec(x: u64x2, k: u64x2) -> u64x2 {
+        let x = u8x16::from_bits(x);
+        u64x2::from_bits(inv_mix(inv_shift_rows(inv_sub_bytes(x)))) ^ k
+    }
+
+    #[inline(always)]
+    pub fn dec_last(x: u64x2, k: u64x2) -> u64x2 {
+        let x = u8x16::from_bits(x);
+        u64x2::from_bits(inv_shift_rows(inv_sub_bytes(x))) ^ k
+    }
+
+    #[inline(always)]
+    pub fn inv_mix_columns(x: u64x2) -> u64x2 {
+        u64x2::from_bits(inv_mix(u8x16::from_bits(x)))
+    }
+
+    /// As `aeskeygenassist` with the constant of key expansion `round`:
+    /// words 1 and 3 through the S-box, each also rotated and xored with the
+    /// round constant.
+    #[inline(always)]
+    pub(super) fn keygen_assist(x: u64x2, round: usize) -> u64x2 {
+        let words = u32x4::from_bits(sub_bytes(u8x16::from_bits(x)));
+        let words: u32x4 = shuffle!(words, [1, 1, 3, 3]);
+        let rcon = u32::from(super::RCON[round]);
+        let assist = words.rotate_right(u32x4::new(0, 8, 0, 8)) ^ u32x4::new(0, rcon, 0, rcon);
+        u64x2::from_bits(assist)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(bytes: [u8; 16]) -> u64x2 {
+        u64x2::from_bits(u8x16::from_slice_unaligned(&bytes))
+    }
+
+    // FIPS-197, appendix C.1: the first and last rounds
+    #[test]
+    fn fips197_rounds() {
+        // round 1 input, round 1 key, round 2 input
+        let start = block([
+            0x00, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80, 0x90, 0xa0, 0xb0, 0xc0, 0xd0,
+            0xe0, 0xf0,
+        ]);
+        let key = block([
+            0xd6, 0xaa, 0x74, 0xfd, 0xd2, 0xaf, 0x72, 0xfa, 0xda, 0xa6, 0x78, 0xf1, 0xd6, 0xab,
+            0x76, 0xfe,
+        ]);
+        let next = block([
+            0x89, 0xd8, 0x10, 0xe8, 0x85, 0x5a, 0xce, 0x68, 0x2d, 0x18, 0x43, 0xd8, 0xcb, 0x12,
+            0x8f, 0xe4,
+        ]);
+        assert_eq!(soft::enc(start, key), next);
+
+        // round 10 input, round 10 key, output
+        let start = block([
+            0xbd, 0x6e, 0x7c, 0x3d, 0xf2, 0xb5, 0x77, 0x9e, 0x0b, 0x61, 0x21, 0x6e, 0x8b, 0x10,
+            0xb6, 0x89,
+        ]);
+        let key = block([
+            0x13, 0x11, 0x1d, 0x7f, 0xe3, 0x94, 0x4a, 0x17, 0xf3, 0x07, 0xa7, 0x8b, 0x4d, 0x2b,
+            0x30, 0xc5,
+        ]);
+        let output = block([
+            0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4,
+            0xc5, 0x5a,
+        ]);
+        assert_eq!(soft::enc_last(start, key), output);
+    }
+
+    fn samples() -> impl Iterator<Item = (u64x2, u64x2)> {
+        let mut rng = SplitMix64::new(0);
+        (0..64).map(move |_| {
+            let mut next = || u64x2::new(rng.next_u64(), rng.next_u64());
+            (next(), next())
+        })
+    }
+
+    #[test]
+    fn soft_inverses() {
+        let zero = u64x2::splat(0);
+        for (x, k) in samples() {
+            let round = soft::inv_mix_columns(soft::enc(x, k) ^ k);
+            assert_eq!(soft::dec_last(round, zero), x);
+            assert_eq!(soft::dec(soft::enc_last(x, zero), zero), soft::inv_mix_columns(x));
+            assert_eq!(soft::dec_last(soft::enc_last(x, k) ^ k, zero), x);
+        }
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "aes"))]
+    #[test]
+    fn soft_matches_aes_ni() {
+        for (x, k) in samples() {
+            assert_eq!(soft::enc(x, k), ni::enc(x, k));
+            assert_eq!(soft::enc_last(x, k), ni::enc_last(x, k));
+            assert_eq!(soft::dec(x, k), ni::dec(x, k));
+            assert_eq!(soft::dec_last(x, k), ni::dec_last(x, k));
+            assert_eq!(soft::inv_mix_columns(x), ni::inv_mix_columns(x));
+            for round in 0..RCON.len() {
+                assert_eq!(soft::keygen_assist(x, round), ni::keygen_assist(x, round));
+            }
+        }
+    }
+}
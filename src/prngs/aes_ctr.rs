@@ -0,0 +1,202 @@
+// AES-128 in counter mode, as NIST SP 800-38A: the counter block is a 128-bit
+// big-endian integer, incremented once per block. Several blocks go through
+// each round together, so one block's `aesenc` latency hides behind the others.
+
+use rand::CryptoRng;
+
+use super::{aes, counter};
+use rng_impl::*;
+
+const BLOCKS: usize = 8;
+
+/// The counter block for `counter`, big-endian.
+#[inline(always)]
+fn counter_block(counter: u128) -> u64x2 {
+    let bytes = u8x16::from_bits(counter::store(counter));
+    let swapped: u8x16 = shuffle!(bytes, [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+    u64x2::from_bits(swapped)
+}
+
+/// Encrypts the counter blocks from `counter` on, round by round across all
+/// of `blocks`.
+#[inline(always)]
+fn encrypt(keys: &[u64x2; 11], counter: u128, blocks: &mut [u64x2]) {
+    for (i, x) in blocks.iter_mut().enumerate() {
+        *x = counter_block(counter.wrapping_add(i as u128)) ^ keys[0];
+    }
+    for key in &keys[1..10] {
+        for x in blocks.iter_mut() {
+            *x = aes::enc(*x, *key);
+        }
+    }
+    for x in blocks.iter_mut() {
+        *x = aes::enc_last(*x, keys[10]);
+    }
+}
+
+/// AES-128 in counter mode.
+///
+/// - Source: NIST SP 800-38A, section 6.5
+/// - State: 128-bit counter, 128-bit key
+/// - Output: 128-bits, the keystream in order
+/// - Cycle Length: 2<sup>128</sup> blocks
+/// - Cryptographic
+///
+/// The full key schedule and 10 rounds, unlike the cheaper key schedules of
+/// [`Ars5`](struct.Ars5.html) and [`AesRand`](struct.AesRand.html). Computes
+/// 8 blocks per refill to keep the AES units busy.
+///
+/// Needs `-C target-feature=+aes` on x86_64 to use AES-NI, which is only
+/// detected at compile time. Other builds use the software rounds in
+/// `aes.rs`, whatever the CPU.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Aes128Ctr {
+    round_keys: [u64x2; 11],
+    /// The counter of the first block after the buffer.
+    ctr: u64x2,
+    buffer: [u64x2; BLOCKS],
+    idx: u8,
+}
+
+impl_debug! { Aes128Ctr { round_keys, ctr, buffer, idx } }
+
+impl_serde! {
+    Aes128Ctr { round_keys, ctr, buffer, idx } where |rng| {
+        if rng.idx as usize <= BLOCKS {
+            Ok(())
+        } else {
+            Err("a buffer index past the blocks")
+        }
+    }
+}
+
+impl_rngcore! { Aes128Ctr }
+
+impl SimdRng for Aes128Ctr {
+    type Result = u64x2;
+
+    #[inline(always)]
+    fn generate(&mut self) -> u64x2 {
+        if self.idx as usize == BLOCKS {
+            let next = counter::load(self.ctr);
+            encrypt(&self.round_keys, next, &mut self.buffer);
+            self.ctr = counter::store(next.wrapping_add(BLOCKS as u128));
+            self.idx = 0;
+        }
+
+        let result = self.buffer[self.idx as usize];
+        self.idx += 1;
+        result
+    }
+}
+
+impl Aes128Ctr {
+    /// The counter block of the next output, as an integer.
+    pub fn counter(&self) -> u128 {
+        let next = counter::load(self.ctr);
+        next.wrapping_sub((BLOCKS - self.idx as usize) as u128)
+    }
+
+    /// Restarts output at the block for `counter`, SP 800-38A's initial
+    /// counter block read as a big-endian integer.
+    pub fn set_counter(&mut self, counter: u128) {
+        self.ctr = counter::store(counter);
+        self.idx = BLOCKS as u8;
+    }
+
+    /// Moves `offset` blocks forward, wrapping at 2^128.
+    pub fn seek(&mut self, offset: u128) {
+        let counter = self.counter().wrapping_add(offset);
+        self.set_counter(counter);
+    }
+
+    /// The block at `counter`, without touching the generator's own counter.
+    pub fn generate_block(&self, counter: u128) -> u64x2 {
+        let mut block = [u64x2::splat(0)];
+        encrypt(&self.round_keys, counter, &mut block);
+        block[0]
+    }
+}
+
+impl CryptoRng for Aes128Ctr {}
+
+impl SeedableRng for Aes128Ctr {
+    /// The AES key, the counter starts at zero.
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self {
+            round_keys: aes::expand_key(seed),
+            ctr: u64x2::splat(0),
+            buffer: [u64x2::splat(0); BLOCKS],
+            idx: BLOCKS as u8,
+        }
+    }
+
+    impl_seed_from_u64! {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // FIPS-197, appendix A.1
+    #[test]
+    fn key_expansion() {
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ];
+        let last = [
+            0xd0, 0x14, 0xf9, 0xa8, 0xc9, 0xee, 0x25, 0x89, 0xe1, 0x3f, 0x0c, 0xc8, 0xb6, 0x63,
+            0x0c, 0xa6,
+        ];
+        assert_eq!(aes::expand_key(key)[10], u64x2::from_bits(u8x16::from_slice_unaligned(&last)));
+    }
+
+    // NIST SP 800-38A, F.5.1 CTR-AES128.Encrypt
+    #[test]
+    fn sp800_38a_ctr() {
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ];
+        let mut rng = Aes128Ctr::from_seed(key);
+        rng.set_counter(0xf0f1f2f3_f4f5f6f7_f8f9fafb_fcfdfeff);
+
+        #[rustfmt::skip]
+        let plaintext = [
+            0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+            0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+            0x30, 0xc8, 0x1c, 0x46, 0xa3, 0x5c, 0xe4, 0x11, 0xe5, 0xfb, 0xc1, 0x19, 0x1a, 0x0a, 0x52, 0xef,
+            0xf6, 0x9f, 0x24, 0x45, 0xdf, 0x4f, 0x9b, 0x17, 0xad, 0x2b, 0x41, 0x7b, 0xe6, 0x6c, 0x37, 0x10,
+        ];
+        #[rustfmt::skip]
+        let ciphertext = [
+            0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6, 0xce,
+            0x98, 0x06, 0xf6, 0x6b, 0x79, 0x70, 0xfd, 0xff, 0x86, 0x17, 0x18, 0x7b, 0xb9, 0xff, 0xfd, 0xff,
+            0x5a, 0xe4, 0xdf, 0x3e, 0xdb, 0xd5, 0xd3, 0x5e, 0x5b, 0x4f, 0x09, 0x02, 0x0d, 0xb0, 0x3e, 0xab,
+            0x1e, 0x03, 0x1d, 0xda, 0x2f, 0xbe, 0x03, 0xd1, 0x79, 0x21, 0x70, 0xa0, 0xf3, 0x00, 0x9c, 0xee,
+        ];
+
+        let mut keystream = [0_u8; 64];
+        rng.fill_bytes(&mut keystream);
+        for ((p, c), k) in plaintext.iter().zip(&ciphertext).zip(&keystream) {
+            assert_eq!(p ^ k, *c);
+        }
+        assert_eq!(rng.counter(), 0xf0f1f2f3_f4f5f6f7_f8f9fafb_fcfdfeff + 4);
+    }
+
+    #[test]
+    fn blocks_match_single() {
+        let rng = Aes128Ctr::from_seed([9; 16]);
+        let mut stream = rng.clone();
+        stream.set_counter(u128::max_value() - 3);
+
+        // the counter wraps around mid-refill
+        for i in 0..BLOCKS as u128 {
+            let counter = (u128::max_value() - 3).wrapping_add(i);
+            assert_eq!(stream.generate(), rng.generate_block(counter));
+        }
+    }
+}
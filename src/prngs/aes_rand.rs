@@ -1,6 +1,4 @@
-use std::arch::x86_64::*;
-use std::{mem, ptr};
-
+use super::{aes, counter};
 use rng_impl::*;
 
 /// AESRand, a counter-based invertible PRNG.
@@ -16,31 +14,144 @@ use rng_impl::*;
 /// Good throughput, decent latency. Easily the best throughput of any 128-bit
 /// PRNG in this library, and better than many 256-bit PRNGs.
 ///
-/// Requires x86 AES support.
+/// Fast only when built with AES-NI, `-C target-feature=+aes` on x86_64. The
+/// choice is made at compile time, without the feature even a CPU that has
+/// AES-NI runs the much slower software rounds in `aes.rs`.
+#[derive(Clone, PartialEq, Eq)]
 pub struct AesRand {
-    state: __m128i,
-    buffer: [__m128i; 2],
+    state: u64x2,
+    buffer: [u64x2; 2],
     full: bool,
+    /// The counter before the first block, for `position`.
+    seed: u64x2,
+}
+
+impl_debug! { AesRand { state, buffer, full, seed } }
+
+impl_serde! { AesRand { state, buffer, full, seed } }
+
+/// AESRand's increment: 1 and the first 15 primes in its bytes, low byte first.
+const INCREMENT: u64x2 = u64x2::new(0x110d_0b07_0503_0201, 0x2f2b_2925_1f1d_1713);
+
+/// The inverse of `aes::enc(x, key)`.
+#[inline(always)]
+fn enc_inverse(x: u64x2, key: u64x2) -> u64x2 {
+    aes::dec_last(aes::inv_mix_columns(x ^ key), u64x2::splat(0))
+}
+
+/// The inverse of `aes::dec(x, key)`. `MixColumns` is `enc` after undoing
+/// the rest of its round with `dec_last`.
+#[inline(always)]
+fn dec_inverse(x: u64x2, key: u64x2) -> u64x2 {
+    let zero = u64x2::splat(0);
+    let mixed = aes::enc(aes::dec_last(x ^ key, zero), zero);
+    aes::enc_last(mixed, zero)
+}
+
+/// The inverse of odd `x` modulo 2^64, by Newton's method.
+fn inverse(x: u64) -> u64 {
+    // correct to 3 bits, each step doubles that
+    let mut y = x;
+    for _ in 0..5 {
+        y = y.wrapping_mul(2_u64.wrapping_sub(x.wrapping_mul(y)));
+    }
+    y
+}
+
+#[inline(always)]
+fn block(state: u64x2) -> [u64x2; 2] {
+    let penultimate = aes::enc(state, INCREMENT);
+    let penultimate1 = aes::enc(penultimate, INCREMENT);
+    let penultimate2 = aes::dec(penultimate, INCREMENT);
+    [penultimate1, penultimate2]
 }
 
 impl AesRand {
     #[inline(always)]
-    pub fn gen_array(&mut self) -> [__m128i; 2] {
-        unsafe {
-            #[rustfmt::skip]
-            let increment = _mm_set_epi8(
-                0x2f, 0x2b, 0x29, 0x25, 0x1f, 0x1d, 0x17, 0x13,
-                0x11, 0x0D, 0x0B, 0x07, 0x05, 0x03, 0x02, 0x01,
-            );
+    pub fn gen_array(&mut self) -> [u64x2; 2] {
+        self.state += INCREMENT;
+        block(self.state)
+    }
+
+    /// The 128-bit counter of the block currently being read.
+    ///
+    /// Unlike the other counter-based generators, each 64-bit half is its
+    /// own Weyl sequence with a distinct odd increment, so consecutive
+    /// blocks don't have consecutive counters.
+    pub fn counter(&self) -> u128 {
+        counter::load(self.state)
+    }
+
+    /// Restarts output at the start of the block for `counter`.
+    pub fn set_counter(&mut self, counter: u128) {
+        self.state = counter::store(counter) - INCREMENT;
+        self.fill_buffer();
+    }
+
+    /// Restarts output `offset` blocks after the current one.
+    pub fn seek(&mut self, offset: u64) {
+        self.set_counter(counter::load(self.state + INCREMENT * offset));
+    }
+
+    /// The block at `counter`, without touching the generator's own counter.
+    pub fn generate_block(&self, counter: u128) -> [u64x2; 2] {
+        block(counter::store(counter))
+    }
+
+    /// The counter of the block a single `generate` output came from.
+    ///
+    /// `generate` returns each block's first half, then its second. Either
+    /// half alone is run back through both rounds, and as AES rounds are
+    /// bijections every output has exactly one counter.
+    pub fn invert(output: u64x2, second_half: bool) -> u128 {
+        let penultimate = if second_half {
+            dec_inverse(output, INCREMENT)
+        } else {
+            enc_inverse(output, INCREMENT)
+        };
+        counter::load(enc_inverse(penultimate, INCREMENT))
+    }
+
+    /// The counter a whole block was generated from, or `None` if its halves
+    /// don't come from the same counter.
+    pub fn invert_block(block: [u64x2; 2]) -> Option<u128> {
+        let counter = Self::invert(block[0], false);
+        if Self::invert(block[1], true) == counter {
+            Some(counter)
+        } else {
+            None
+        }
+    }
+
+    /// How many blocks came before the one currently being read, since
+    /// seeding.
+    ///
+    /// After `set_counter` to a counter this seed never reaches, this counts
+    /// in the low 64-bit half only.
+    pub fn position(&self) -> u64 {
+        self.steps(counter::load(self.state))
+            .extract(0)
+            .wrapping_sub(1)
+    }
 
-            self.state = _mm_add_epi64(self.state, increment);
-            let penultimate = _mm_aesenc_si128(self.state, increment);
-            let penultimate1 = _mm_aesenc_si128(penultimate, increment);
-            let penultimate2 = _mm_aesdec_si128(penultimate, increment);
-            [penultimate1, penultimate2]
+    /// The position of the block at `counter`, if this generator's seed
+    /// reaches it. With `invert`, finds which draw produced an output.
+    pub fn position_of(&self, counter: u128) -> Option<u64> {
+        let steps = self.steps(counter);
+        if steps.extract(0) == steps.extract(1) {
+            Some(steps.extract(0).wrapping_sub(1))
+        } else {
+            None
         }
     }
 
+    /// Increments from the seed to `counter`, in each 64-bit half.
+    fn steps(&self, counter: u128) -> u64x2 {
+        let distance = counter::store(counter) - self.seed;
+        let inverse = u64x2::new(inverse(INCREMENT.extract(0)), inverse(INCREMENT.extract(1)));
+        distance * inverse
+    }
+
     #[inline(always)]
     fn fill_buffer(&mut self) {
         self.buffer = self.gen_array();
@@ -48,11 +159,37 @@ impl AesRand {
     }
 }
 
+const CHUNK_SIZE: usize = 16;
+
+/// Writes `blocks` to `dest` in order, as many bytes as fit.
+#[inline(always)]
+fn write_blocks(dest: &mut [u8], blocks: &[u64x2]) {
+    for (chunk, &block) in dest.chunks_mut(CHUNK_SIZE).zip(blocks) {
+        let bytes = u8x16::from_bits(block);
+        if chunk.len() == CHUNK_SIZE {
+            bytes.write_to_slice_unaligned(chunk);
+        } else {
+            let mut full = [0; CHUNK_SIZE];
+            bytes.write_to_slice_unaligned(&mut full);
+            chunk.copy_from_slice(&full[..chunk.len()]);
+        }
+    }
+}
+
+/// Stateless [`AesRand`](struct.AesRand.html): block `counter` of the
+/// generator seeded with `key`.
+///
+/// AESRand has no key of its own, its seed is the starting point of the
+/// counter. Block `n` is at `key + (n + 1) * increment` in each 64-bit half.
+pub fn aes_rand_at(key: u128, counter: u64) -> [u64x2; 2] {
+    block(counter::store(key) + INCREMENT * counter.wrapping_add(1))
+}
+
 impl SimdRng for AesRand {
-    type Result = __m128i;
+    type Result = u64x2;
 
     #[inline(always)]
-    fn generate(&mut self) -> __m128i {
+    fn generate(&mut self) -> u64x2 {
         if self.full {
             self.full = false;
             self.buffer[0]
@@ -67,53 +204,24 @@ impl SimdRng for AesRand {
 impl RngCore for AesRand {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
-        unsafe { _mm_extract_epi32(self.generate(), 0) as u32 }
+        u32x4::from_bits(self.generate()).extract(0)
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
-        unsafe { _mm_extract_epi64(self.generate(), 0) as u64 }
+        self.generate().extract(0)
     }
 
     // Custom implementation to best use the two outputs.
     #[inline(always)]
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        const CHUNK_SIZE: usize = mem::size_of::<__m128i>();
-
         for large_chunk in dest.chunks_exact_mut(CHUNK_SIZE * 2) {
-            for (ch, &res) in large_chunk
-                .chunks_exact_mut(CHUNK_SIZE)
-                .zip(&self.gen_array())
-            {
-                #[allow(clippy::cast_ptr_alignment)]
-                let ptr = ch.as_mut_ptr() as *mut __m128i;
-                unsafe {
-                    _mm_storeu_si128(ptr, res);
-                }
-            }
-        }
-
-        let large_remainder = dest.chunks_exact_mut(CHUNK_SIZE * 2).into_remainder();
-
-        for chunk in large_remainder.chunks_exact_mut(CHUNK_SIZE) {
-            let results = self.generate();
-            #[allow(clippy::cast_ptr_alignment)]
-            let ptr = chunk.as_mut_ptr() as *mut __m128i;
-            unsafe {
-                _mm_storeu_si128(ptr, results);
-            }
+            write_blocks(large_chunk, &self.gen_array());
         }
 
-        let remainder = large_remainder
-            .chunks_exact_mut(CHUNK_SIZE)
-            .into_remainder();
-        if !remainder.is_empty() {
-            let results = self.generate();
-            let src_ptr = &results as *const __m128i as *const u8;
-            let dst_ptr = remainder.as_mut_ptr();
-            unsafe {
-                ptr::copy_nonoverlapping(src_ptr, dst_ptr, remainder.len());
-            }
+        let remainder = dest.chunks_exact_mut(CHUNK_SIZE * 2).into_remainder();
+        for chunk in remainder.chunks_mut(CHUNK_SIZE) {
+            write_blocks(chunk, &[self.generate()]);
         }
     }
 
@@ -124,18 +232,243 @@ impl RngCore for AesRand {
     }
 }
 
+impl SplittableRng for AesRand {
+    /// The child starts its counter at the parent's next output.
+    fn split(&mut self) -> Self {
+        let state = self.generate();
+        let mut rng = Self {
+            state,
+            buffer: [u64x2::splat(0); 2],
+            full: false,
+            seed: state,
+        };
+        rng.fill_buffer();
+        rng
+    }
+}
+
 impl SeedableRng for AesRand {
     type Seed = [u8; 16];
 
     fn from_seed(seed: Self::Seed) -> Self {
-        #[allow(clippy::cast_ptr_alignment)]
-        let ptr = seed.as_ptr() as *const __m128i;
+        let state = u64x2::from_bits(u8x16::from_slice_unaligned(&seed));
         let mut rng = Self {
-            state: unsafe { _mm_loadu_si128(ptr) },
-            buffer: [unsafe { _mm_setzero_si128() }; 2],
+            state,
+            buffer: [u64x2::splat(0); 2],
             full: false,
+            seed: state,
         };
         rng.fill_buffer();
         rng
     }
+
+    impl_seed_from_u64! {}
+}
+
+/// The Weyl increment of stream `i` of a multi-stream AESRand.
+///
+/// Stream 0 steps by AESRand's own increment, the others by it plus `2 * i`
+/// in each 64-bit half, so every increment stays odd and no two are equal.
+#[inline(always)]
+fn stream_increment(i: usize) -> u64x2 {
+    INCREMENT + 2 * i as u64
+}
+
+macro_rules! make_aes_rand_x {
+    ($rng_name:ident, $streams:expr, $seed:ty) => {
+        /// [`AesRand`](struct.AesRand.html) with several independent counters
+        /// stepped in lock-step.
+        ///
+        /// Each stream is its own AESRand counter with a distinct increment,
+        /// and their blocks are computed together so the `aesenc` latency of
+        /// one hides behind the others. Outputs go through each stream's
+        /// two-block output in turn.
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $rng_name {
+            state: [u64x2; $streams],
+            buffer: [u64x2; 2 * $streams],
+            idx: u8,
+        }
+
+        impl_debug! { $rng_name { state, buffer, idx } }
+
+        impl_serde! {
+            $rng_name { state, buffer, idx } where |rng| {
+                if rng.idx as usize <= 2 * $streams {
+                    Ok(())
+                } else {
+                    Err("a buffer index past the blocks")
+                }
+            }
+        }
+
+        impl $rng_name {
+            /// Steps every stream and returns their blocks, stream `i` at
+            /// `2 * i` and `2 * i + 1`.
+            #[inline(always)]
+            pub fn gen_array(&mut self) -> [u64x2; 2 * $streams] {
+                let mut penultimate = [u64x2::splat(0); $streams];
+                for (i, (s, p)) in self.state.iter_mut().zip(&mut penultimate).enumerate() {
+                    *s += stream_increment(i);
+                    *p = aes::enc(*s, INCREMENT);
+                }
+
+                let mut blocks = [u64x2::splat(0); 2 * $streams];
+                for (b, &p) in blocks.chunks_exact_mut(2).zip(&penultimate) {
+                    b[0] = aes::enc(p, INCREMENT);
+                    b[1] = aes::dec(p, INCREMENT);
+                }
+                blocks
+            }
+        }
+
+        impl SimdRng for $rng_name {
+            type Result = u64x2;
+
+            #[inline(always)]
+            fn generate(&mut self) -> u64x2 {
+                if self.idx as usize == 2 * $streams {
+                    self.buffer = self.gen_array();
+                    self.idx = 0;
+                }
+
+                let result = self.buffer[self.idx as usize];
+                self.idx += 1;
+                result
+            }
+        }
+
+        impl RngCore for $rng_name {
+            #[inline(always)]
+            fn next_u32(&mut self) -> u32 {
+                u32x4::from_bits(self.generate()).extract(0)
+            }
+
+            #[inline(always)]
+            fn next_u64(&mut self) -> u64 {
+                self.generate().extract(0)
+            }
+
+            // Custom implementation to use every stream's outputs at once.
+            #[inline(always)]
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                for large_chunk in dest.chunks_exact_mut(CHUNK_SIZE * 2 * $streams) {
+                    write_blocks(large_chunk, &self.gen_array());
+                }
+
+                let remainder = dest
+                    .chunks_exact_mut(CHUNK_SIZE * 2 * $streams)
+                    .into_remainder();
+                for chunk in remainder.chunks_mut(CHUNK_SIZE) {
+                    write_blocks(chunk, &[self.generate()]);
+                }
+            }
+
+            #[inline(always)]
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        impl SeedableRng for $rng_name {
+            /// The starting counter of each stream, 16 bytes each.
+            type Seed = $seed;
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                let mut state = [u64x2::splat(0); $streams];
+                for (s, chunk) in state.iter_mut().zip(seed.as_ref().chunks_exact(16)) {
+                    *s = u64x2::from_bits(u8x16::from_slice_unaligned(chunk));
+                }
+
+                Self {
+                    state,
+                    buffer: [u64x2::splat(0); 2 * $streams],
+                    idx: 2 * $streams,
+                }
+            }
+
+            impl_seed_from_u64! {}
+        }
+    };
+}
+
+make_aes_rand_x! { AesRandX2, 2, [u8; 32] }
+make_aes_rand_x! { AesRandX4, 4, Seed512 }
+make_aes_rand_x! { AesRandX8, 8, Seed1024 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_are_aes_rand() {
+        let mut seed = [0; 32];
+        for (i, x) in seed.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        let mut multi = AesRandX2::from_seed(seed);
+        let mut first = [0; 16];
+        first.copy_from_slice(&seed[..16]);
+        let mut single = AesRand::from_seed(first);
+
+        // stream 0 steps exactly like `AesRand`, stream 1 by a different amount
+        for _ in 0..3 {
+            assert_eq!(multi.gen_array()[..2], single.gen_array());
+        }
+
+        let start = u64x2::from_bits(u8x16::from_slice_unaligned(&seed[16..]));
+        assert_eq!(multi.state[1] - start, (INCREMENT + 2) * 3);
+    }
+
+    #[test]
+    fn invert_finds_the_draw() {
+        let mut rng = AesRand::seed_from_u64(11);
+        assert_eq!(rng.position(), 0);
+
+        for n in 1..5 {
+            let block = rng.gen_array();
+            assert_eq!(rng.position(), n);
+
+            let counter = AesRand::invert_block(block).unwrap();
+            assert_eq!(counter, rng.counter());
+            assert_eq!(rng.position_of(counter), Some(n));
+        }
+
+        let mut block = rng.gen_array();
+        block[1] += 1;
+        assert_eq!(AesRand::invert_block(block), None);
+
+        // a counter with the halves at different positions
+        let counter = rng.counter().wrapping_add(1);
+        assert_eq!(rng.position_of(counter), None);
+    }
+
+    #[test]
+    fn invert_single_outputs() {
+        let mut rng = AesRand::seed_from_u64(3);
+        let start = rng.clone();
+
+        // a logged output and whether it was a second half is enough
+        for n in 0..6 {
+            let output = rng.generate();
+            let counter = AesRand::invert(output, n % 2 == 1);
+            assert_eq!(start.position_of(counter), Some(n / 2));
+        }
+    }
+
+    #[test]
+    fn fill_bytes_matches_generate() {
+        let mut a = AesRandX4::seed_from_u64(5);
+        let mut b = a.clone();
+
+        let mut bytes = [0; 16 * 8 + 16 * 3 + 5];
+        a.fill_bytes(&mut bytes);
+        for chunk in bytes.chunks(16) {
+            let x = u8x16::from_bits(b.generate());
+            let mut expected = [0; 16];
+            x.write_to_slice_unaligned(&mut expected);
+            assert_eq!(chunk, &expected[..chunk.len()]);
+        }
+    }
 }
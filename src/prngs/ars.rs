@@ -1,74 +1,107 @@
 // When only one 128-bit stream is used, there is no worry of correlation. If
 // multiple streams are used, it is trivial to avoid correlation by setting
-// the `input` counter appropriately
+// the counter appropriately (see `set_counter`)
 //
-// I do not know if a `aarch64` implementation is possible
-
-use std::arch::x86_64::*;
+// Without AES-NI, the rounds are done in software, see `aes.rs`
 
+use super::{aes, counter};
 use rng_impl::*;
 
-#[inline(always)]
-fn aes_enc(x: u64x2, k: u64x2) -> u64x2 {
-    let x = __m128i::from_bits(x);
-    let k = __m128i::from_bits(k);
-
-    let r = unsafe { _mm_aesenc_si128(x, k) };
-    u64x2::from_bits(r)
-}
-
-#[inline(always)]
-fn aes_enc_last(x: u64x2, k: u64x2) -> u64x2 {
-    let x = __m128i::from_bits(x);
-    let k = __m128i::from_bits(k);
-
-    let r = unsafe { _mm_aesenclast_si128(x, k) };
-    u64x2::from_bits(r)
-}
-
 const KEY_WEYL: u64x2 = u64x2::new(
     0xbb67ae8584caa73b, // sqrt(3) - 1.0
     0x9e3779b97f4a7c15, // golden ratio
 );
 
+#[inline(always)]
+fn ars(input: u64x2, key: u64x2, rounds: usize) -> u64x2 {
+    let mut kk = key;
+    let mut v = input ^ kk;
+
+    // final round is `aes::enc_last`
+    for _round in 0..rounds - 1 {
+        kk += KEY_WEYL;
+        v = aes::enc(v, kk);
+    }
+
+    kk += KEY_WEYL;
+    aes::enc_last(v, kk)
+}
+
 /// ARS-5 from [Random123]
 ///
 /// A single stream
 ///
 /// 4 rounds is not "Crush-resistant" (ARS-4 gets >256GB with PractRand)
 ///
+/// AES-NI is only used when enabled at compile time, with
+/// `-C target-feature=+aes` on x86_64. Without it the rounds run in software,
+/// even on CPUs that have the instructions.
+///
 /// [Random123]: http://www.deshawresearch.com/resources_random123.html
+#[derive(Clone, PartialEq, Eq)]
 pub struct Ars5 {
     input: u64x2,
     key: u64x2,
 }
 
+impl_debug! { Ars5 { input, key } }
+
+impl_serde! { Ars5 { input, key } }
+
 impl SimdRng for Ars5 {
     type Result = u64x2;
 
     #[inline(always)]
     fn generate(&mut self) -> u64x2 {
-        let mut kk = self.key;
-        let mut v = self.input ^ kk;
-
-        // Do we need exact u128 increment math here? Or is any
-        // 2^128-period Weyl-style sequence sufficient? (Currently just
-        // SIMD addition for simplicity, unsure of the period).
-        self.input += u64x2::new(1, 2);
-
-        // final round is `aes_enc_last`
-        for _round in 0..5 - 1 {
-            kk += KEY_WEYL;
-            v = aes_enc(v, kk);
-        }
+        let result = ars(self.input, self.key, 5);
+        self.input = counter::store(counter::load(self.input).wrapping_add(1));
+        result
+    }
+}
 
-        kk += KEY_WEYL;
-        aes_enc_last(v, kk)
+impl Ars5 {
+    /// The 128-bit counter of the next block.
+    pub fn counter(&self) -> u128 {
+        counter::load(self.input)
+    }
+
+    /// Restarts output at block `counter`, one block per `generate`.
+    pub fn set_counter(&mut self, counter: u128) {
+        self.input = counter::store(counter);
+    }
+
+    /// Moves `offset` blocks forward, wrapping at 2^128.
+    pub fn seek(&mut self, offset: u128) {
+        let counter = self.counter().wrapping_add(offset);
+        self.set_counter(counter);
+    }
+
+    /// The block at `counter`, without touching the generator's own counter.
+    pub fn generate_block(&self, counter: u128) -> u64x2 {
+        ars(counter::store(counter), self.key, 5)
     }
 }
 
 impl_rngcore! { Ars5 }
 
+impl SplittableRng for Ars5 {
+    /// The child is keyed with the parent's next block, starting at counter
+    /// zero.
+    fn split(&mut self) -> Self {
+        Self {
+            input: u64x2::splat(0),
+            key: self.generate(),
+        }
+    }
+}
+
+/// Stateless [`Ars5`](struct.Ars5.html): the block at `counter` for `key`.
+///
+/// Matches `Ars5::generate_block` on a generator seeded with `key`.
+pub fn ars5_at(key: u128, counter: u128) -> u64x2 {
+    ars(counter::store(counter), counter::store(key), 5)
+}
+
 impl SeedableRng for Ars5 {
     type Seed = [u8; 32];
 
@@ -80,44 +113,82 @@ impl SeedableRng for Ars5 {
             key: load(&seed[16..]),
         }
     }
+
+    impl_seed_from_u64! {}
 }
 
 /// ARS-7 from [Random123]
 ///
 /// A single stream
 ///
+/// Like [`Ars5`](struct.Ars5.html), needs `-C target-feature=+aes` at
+/// compile time for AES-NI.
+///
 /// [Random123]: http://www.deshawresearch.com/resources_random123.html
+#[derive(Clone, PartialEq, Eq)]
 pub struct Ars7 {
     input: u64x2,
     key: u64x2,
 }
 
+impl_debug! { Ars7 { input, key } }
+
+impl_serde! { Ars7 { input, key } }
+
 impl SimdRng for Ars7 {
     type Result = u64x2;
 
     #[inline(always)]
     fn generate(&mut self) -> u64x2 {
-        let mut kk = self.key;
-        let mut v = self.input ^ kk;
-
-        // Do we need exact u128 increment math here? Or is any
-        // 2^128-period Weyl-style sequence sufficient? (Currently just
-        // SIMD addition for simplicity, unsure of the period).
-        self.input += u64x2::new(1, 2);
-
-        // final round is `aes_enc_last`
-        for _round in 0..7 - 1 {
-            kk += KEY_WEYL;
-            v = aes_enc(v, kk);
-        }
+        let result = ars(self.input, self.key, 7);
+        self.input = counter::store(counter::load(self.input).wrapping_add(1));
+        result
+    }
+}
 
-        kk += KEY_WEYL;
-        aes_enc_last(v, kk)
+impl Ars7 {
+    /// The 128-bit counter of the next block.
+    pub fn counter(&self) -> u128 {
+        counter::load(self.input)
+    }
+
+    /// Restarts output at block `counter`, one block per `generate`.
+    pub fn set_counter(&mut self, counter: u128) {
+        self.input = counter::store(counter);
+    }
+
+    /// Moves `offset` blocks forward, wrapping at 2^128.
+    pub fn seek(&mut self, offset: u128) {
+        let counter = self.counter().wrapping_add(offset);
+        self.set_counter(counter);
+    }
+
+    /// The block at `counter`, without touching the generator's own counter.
+    pub fn generate_block(&self, counter: u128) -> u64x2 {
+        ars(counter::store(counter), self.key, 7)
     }
 }
 
 impl_rngcore! { Ars7 }
 
+impl SplittableRng for Ars7 {
+    /// The child is keyed with the parent's next block, starting at counter
+    /// zero.
+    fn split(&mut self) -> Self {
+        Self {
+            input: u64x2::splat(0),
+            key: self.generate(),
+        }
+    }
+}
+
+/// Stateless [`Ars7`](struct.Ars7.html): the block at `counter` for `key`.
+///
+/// Matches `Ars7::generate_block` on a generator seeded with `key`.
+pub fn ars7_at(key: u128, counter: u128) -> u64x2 {
+    ars(counter::store(counter), counter::store(key), 7)
+}
+
 impl SeedableRng for Ars7 {
     type Seed = [u8; 32];
 
@@ -129,4 +200,28 @@ impl SeedableRng for Ars7 {
             key: load(&seed[16..]),
         }
     }
+
+    impl_seed_from_u64! {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_is_deterministic() {
+        let mut a = Ars5::seed_from_u64(0);
+        let mut b = a.clone();
+
+        let mut child_a = a.split();
+        let mut child_b = b.split();
+        assert_eq!(a, b);
+        assert_eq!(child_a, child_b);
+
+        // the parent moved past the block it keyed the child with
+        assert_eq!(a.counter(), Ars5::seed_from_u64(0).counter().wrapping_add(1));
+        let first = child_a.generate();
+        assert_eq!(first, child_b.generate());
+        assert_ne!(first, a.generate());
+    }
 }
@@ -2,6 +2,9 @@
 //!
 //! https://cr.yp.to/chacha.html
 
+use rand::CryptoRng;
+
+use super::counter;
 use rng_impl::*;
 
 const CHACHA_SEED: u32x4 = u32x4::new(0x61707865, 0x3320646E, 0x79622D32, 0x6B206574);
@@ -18,6 +21,7 @@ const CHACHA_SEED: u32x4 = u32x4::new(0x61707865, 0x3320646E, 0x79622D32, 0x6B20
 ///
 /// - Memory: 64 bytes
 /// - Speed: around half of [`Ars5`](struct.Ars5.html)
+#[derive(Clone, PartialEq, Eq)]
 pub struct ChaCha4 {
     a: u32x4,
     b: u32x4,
@@ -25,6 +29,10 @@ pub struct ChaCha4 {
     d: u32x4,
 }
 
+impl_debug! { ChaCha4 { a, b, c, d } }
+
+impl_serde! { ChaCha4 { a, b, c, d } }
+
 impl_rngcore! { ChaCha4 }
 
 impl SimdRng for ChaCha4 {
@@ -32,10 +40,43 @@ impl SimdRng for ChaCha4 {
 
     #[inline(always)]
     fn generate(&mut self) -> u32x16 {
+        let result = self.block(self.d);
+        self.seek(1);
+        result
+    }
+}
+
+impl ChaCha4 {
+    /// The 128-bit counter of the next block: the 64-bit block counter in
+    /// the low half and the nonce in the high half, so it wraps into the
+    /// nonce like a plain 128-bit integer.
+    pub fn counter(&self) -> u128 {
+        counter::load(u64x2::from_bits(self.d))
+    }
+
+    /// Restarts output at block `counter`, one block per `generate`. The
+    /// high 64 bits set the nonce.
+    pub fn set_counter(&mut self, counter: u128) {
+        self.d = u32x4::from_bits(counter::store(counter));
+    }
+
+    /// Moves `offset` blocks forward, wrapping at 2^128.
+    pub fn seek(&mut self, offset: u128) {
+        let counter = self.counter().wrapping_add(offset);
+        self.set_counter(counter);
+    }
+
+    /// The block at `counter`, without touching the generator's own counter.
+    pub fn generate_block(&self, counter: u128) -> u32x16 {
+        self.block(u32x4::from_bits(counter::store(counter)))
+    }
+
+    #[inline(always)]
+    fn block(&self, counter: u32x4) -> u32x16 {
         let mut a = self.a;
         let mut b = self.b;
         let mut c = self.c;
-        let mut d = self.d;
+        let mut d = counter;
 
         #[rustfmt::skip]
         macro_rules! round {
@@ -61,10 +102,7 @@ impl SimdRng for ChaCha4 {
         a += self.a;
         b += self.b;
         c += self.c;
-        d += self.d;
-
-        // update 64-bit counter
-        self.d = u32x4::from_bits(u64x2::from_bits(self.d) + 1);
+        d += counter;
 
         let ab: u32x8 = shuffle!(a, b, [0, 1, 2, 3, 4, 5, 6, 7]);
         let cd: u32x8 = shuffle!(c, d, [0, 1, 2, 3, 4, 5, 6, 7]);
@@ -76,6 +114,29 @@ impl SimdRng for ChaCha4 {
     }
 }
 
+/// Stateless [`ChaCha4`](struct.ChaCha4.html): the block at `counter` for
+/// `key`.
+///
+/// `key` is the 256-bit seed of `from_seed`, and `counter` is as in
+/// `ChaCha4::set_counter`.
+pub fn chacha4_at(key: [u8; 32], counter: u128) -> u32x16 {
+    ChaCha4::from_seed(key).generate_block(counter)
+}
+
+impl SplittableRng for ChaCha4 {
+    /// The child is keyed with the first half of the parent's next block,
+    /// starting at counter zero.
+    fn split(&mut self) -> Self {
+        let block = self.generate();
+        Self {
+            a: CHACHA_SEED,
+            b: shuffle!(block, [0, 1, 2, 3]),
+            c: shuffle!(block, [4, 5, 6, 7]),
+            d: u32x4::splat(0),
+        }
+    }
+}
+
 impl SeedableRng for ChaCha4 {
     type Seed = [u8; 32];
 
@@ -87,6 +148,8 @@ impl SeedableRng for ChaCha4 {
             d: u32x4::splat(0),                                            // counter
         }
     }
+
+    impl_seed_from_u64! {}
 }
 
 /// A faster variant of [`ChaCha4`](struct.ChaCha4.html)
@@ -108,6 +171,7 @@ impl SeedableRng for ChaCha4 {
 ///
 /// - Memory: 64 bytes
 /// - Speed: around 1.3 times [`ChaCha4`](struct.ChaCha4.html)
+#[derive(Clone, PartialEq, Eq)]
 pub struct ChaChaAlt4 {
     a: u32x4,
     b: u32x4,
@@ -115,6 +179,10 @@ pub struct ChaChaAlt4 {
     d: u32x4,
 }
 
+impl_debug! { ChaChaAlt4 { a, b, c, d } }
+
+impl_serde! { ChaChaAlt4 { a, b, c, d } }
+
 impl_rngcore! { ChaChaAlt4 }
 
 impl SimdRng for ChaChaAlt4 {
@@ -122,10 +190,43 @@ impl SimdRng for ChaChaAlt4 {
 
     #[inline(always)]
     fn generate(&mut self) -> u32x16 {
+        let result = self.block(self.d);
+        self.seek(1);
+        result
+    }
+}
+
+impl ChaChaAlt4 {
+    /// The 128-bit counter of the next block: the 64-bit block counter in
+    /// the low half and the nonce in the high half, so it wraps into the
+    /// nonce like a plain 128-bit integer.
+    pub fn counter(&self) -> u128 {
+        counter::load(u64x2::from_bits(self.d))
+    }
+
+    /// Restarts output at block `counter`, one block per `generate`. The
+    /// high 64 bits set the nonce.
+    pub fn set_counter(&mut self, counter: u128) {
+        self.d = u32x4::from_bits(counter::store(counter));
+    }
+
+    /// Moves `offset` blocks forward, wrapping at 2^128.
+    pub fn seek(&mut self, offset: u128) {
+        let counter = self.counter().wrapping_add(offset);
+        self.set_counter(counter);
+    }
+
+    /// The block at `counter`, without touching the generator's own counter.
+    pub fn generate_block(&self, counter: u128) -> u32x16 {
+        self.block(u32x4::from_bits(counter::store(counter)))
+    }
+
+    #[inline(always)]
+    fn block(&self, counter: u32x4) -> u32x16 {
         let mut a = self.a;
         let mut b = self.b;
         let mut c = self.c;
-        let mut d = self.d;
+        let mut d = counter;
 
         #[rustfmt::skip]
         macro_rules! round {
@@ -151,12 +252,8 @@ impl SimdRng for ChaChaAlt4 {
         a += self.a;
         b += self.b;
         c += self.c;
-        d += self.d;
+        d += counter;
 
-        // update 64-bit counter
-        self.d = u32x4::from_bits(u64x2::from_bits(self.d) + 1);
-
-        // panic!();
         let ab: u32x8 = shuffle!(a, b, [0, 1, 2, 3, 4, 5, 6, 7]);
         let cd: u32x8 = shuffle!(c, d, [0, 1, 2, 3, 4, 5, 6, 7]);
         shuffle!(
@@ -167,6 +264,29 @@ impl SimdRng for ChaChaAlt4 {
     }
 }
 
+/// Stateless [`ChaChaAlt4`](struct.ChaChaAlt4.html): the block at `counter` for
+/// `key`.
+///
+/// `key` is the 256-bit seed of `from_seed`, and `counter` is as in
+/// `ChaChaAlt4::set_counter`.
+pub fn chacha_alt4_at(key: [u8; 32], counter: u128) -> u32x16 {
+    ChaChaAlt4::from_seed(key).generate_block(counter)
+}
+
+impl SplittableRng for ChaChaAlt4 {
+    /// The child is keyed with the first half of the parent's next block,
+    /// starting at counter zero.
+    fn split(&mut self) -> Self {
+        let block = self.generate();
+        Self {
+            a: CHACHA_SEED,
+            b: shuffle!(block, [0, 1, 2, 3]),
+            c: shuffle!(block, [4, 5, 6, 7]),
+            d: u32x4::splat(0),
+        }
+    }
+}
+
 impl SeedableRng for ChaChaAlt4 {
     type Seed = [u8; 32];
 
@@ -178,4 +298,404 @@ impl SeedableRng for ChaChaAlt4 {
             d: u32x4::splat(0),                                            // counter
         }
     }
+
+    impl_seed_from_u64! {}
+}
+
+/// HChaCha20, for deriving XChaCha subkeys: the ChaCha20 rounds on `key` and
+/// a 128-bit `input` in place of counter and nonce, keeping the first and last
+/// rows without the final addition.
+///
+/// - Source: <https://tools.ietf.org/html/draft-irtf-cfrg-xchacha-03>
+pub fn hchacha20(key: [u8; 32], input: [u8; 16]) -> [u8; 32] {
+    let mut a = CHACHA_SEED;
+    let mut b = u32x4::from_bits(u8x16::from_slice_unaligned(&key[..16]));
+    let mut c = u32x4::from_bits(u8x16::from_slice_unaligned(&key[16..]));
+    let mut d = u32x4::from_bits(u8x16::from_slice_unaligned(&input));
+
+    #[rustfmt::skip]
+    macro_rules! round {
+        () => {{
+            a += b; d ^= a; d = d.rotate_left_opt(16);
+            c += d; b ^= c; b = b.rotate_left_opt(12);
+            a += b; d ^= a; d = d.rotate_left_opt(8);
+            c += d; b ^= c; b = b.rotate_left_opt(7);
+        }};
+    }
+
+    for _double_round in 0..10 {
+        // columns
+        round!();
+
+        // diagonals
+        b = shuffle!(b, [1, 2, 3, 0]);
+        c = shuffle!(c, [2, 3, 0, 1]);
+        d = shuffle!(d, [3, 0, 1, 2]);
+        round!();
+        b = shuffle!(b, [3, 0, 1, 2]);
+        c = shuffle!(c, [2, 3, 0, 1]);
+        d = shuffle!(d, [1, 2, 3, 0]);
+    }
+
+    let mut subkey = [0; 32];
+    u8x16::from_bits(a).write_to_slice_unaligned(&mut subkey[..16]);
+    u8x16::from_bits(d).write_to_slice_unaligned(&mut subkey[16..]);
+    subkey
+}
+
+/// Transposes the rows of two interleaved blocks, each block in one half of
+/// every row, to one block per vector.
+#[inline(always)]
+fn transpose_x8(a: u32x8, b: u32x8, c: u32x8, d: u32x8) -> [u32x16; 2] {
+    let ab0: u32x8 = shuffle!(a, b, [0, 1, 2, 3, 8, 9, 10, 11]);
+    let ab1: u32x8 = shuffle!(a, b, [4, 5, 6, 7, 12, 13, 14, 15]);
+    let cd0: u32x8 = shuffle!(c, d, [0, 1, 2, 3, 8, 9, 10, 11]);
+    let cd1: u32x8 = shuffle!(c, d, [4, 5, 6, 7, 12, 13, 14, 15]);
+    [
+        shuffle!(ab0, cd0, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]),
+        shuffle!(ab1, cd1, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]),
+    ]
+}
+
+/// Transposes the rows of four interleaved blocks, each block in a quarter
+/// of every row, to one block per vector.
+#[inline(always)]
+fn transpose_x16(a: u32x16, b: u32x16, c: u32x16, d: u32x16) -> [u32x16; 4] {
+    // rows `a` and `b` (or `c` and `d`) of blocks 0 and 1, then of 2 and 3
+    let lo = |x: u32x16, y: u32x16| -> u32x16 {
+        shuffle!(x, y, [0, 1, 2, 3, 16, 17, 18, 19, 4, 5, 6, 7, 20, 21, 22, 23])
+    };
+    let hi = |x: u32x16, y: u32x16| -> u32x16 {
+        shuffle!(x, y, [8, 9, 10, 11, 24, 25, 26, 27, 12, 13, 14, 15, 28, 29, 30, 31])
+    };
+    let (ab_lo, ab_hi, cd_lo, cd_hi) = (lo(a, b), hi(a, b), lo(c, d), hi(c, d));
+
+    let first = |x: u32x16, y: u32x16| -> u32x16 {
+        shuffle!(x, y, [0, 1, 2, 3, 4, 5, 6, 7, 16, 17, 18, 19, 20, 21, 22, 23])
+    };
+    let second = |x: u32x16, y: u32x16| -> u32x16 {
+        shuffle!(x, y, [8, 9, 10, 11, 12, 13, 14, 15, 24, 25, 26, 27, 28, 29, 30, 31])
+    };
+    [first(ab_lo, cd_lo), second(ab_lo, cd_lo), first(ab_hi, cd_hi), second(ab_hi, cd_hi)]
+}
+
+macro_rules! make_chacha {
+    (
+        $rng_name:ident, $vector:ident, $u128xN:ident, $u64xN:ident, $blocks:expr, $rounds:expr,
+        $offsets:expr, $rot1:tt, $rot2:tt, $rot3:tt, $transpose:ident
+    ) => {
+        /// Full-strength ChaCha, with the round count in its name.
+        ///
+        /// Computes several blocks per call, each row of the state holding
+        /// that row of every block:
+        /// [*Vectorization of ChaCha Stream Cipher*](https://eprint.iacr.org/2013/759.pdf).
+        /// Outputs are the keystream in order, one block at a time.
+        ///
+        /// The 64-bit block counter and 64-bit stream id are the original
+        /// ChaCha layout. RFC 7539's 32-bit counter and 96-bit nonce are the
+        /// same words, see `set_counter`.
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $rng_name {
+            b: u32x4,
+            c: u32x4,
+            d: u32x4,
+            buffer: [u32x16; $blocks],
+            idx: u8,
+        }
+
+        impl_debug! { $rng_name { b, c, d, buffer, idx } }
+
+        impl_serde! {
+            $rng_name { b, c, d, buffer, idx } where |rng| {
+                if rng.idx as usize <= $blocks {
+                    Ok(())
+                } else {
+                    Err("a buffer index past the blocks")
+                }
+            }
+        }
+
+        impl_rngcore! { $rng_name }
+
+        impl SimdRng for $rng_name {
+            type Result = u32x16;
+
+            #[inline(always)]
+            fn generate(&mut self) -> u32x16 {
+                if self.idx as usize == $blocks {
+                    self.buffer = self.blocks();
+                    self.idx = 0;
+                    self.d = u32x4::from_bits(u64x2::from_bits(self.d) + u64x2::new($blocks, 0));
+                }
+
+                let result = self.buffer[self.idx as usize];
+                self.idx += 1;
+                result
+            }
+        }
+
+        impl $rng_name {
+            /// XChaCha: HChaCha20 derives a subkey from `key` and the first 16
+            /// bytes of `nonce`, the last 8 bytes are the stream.
+            ///
+            /// With 192 bits, nonces chosen at random won't collide, so every
+            /// stream can be given one without coordination.
+            pub fn with_extended_nonce(key: [u8; 32], nonce: [u8; 24]) -> Self {
+                let mut input = [0; 16];
+                input.copy_from_slice(&nonce[..16]);
+                let mut stream = [0; 8];
+                stream.copy_from_slice(&nonce[16..]);
+
+                let mut rng = Self::from_seed(hchacha20(key, input));
+                rng.set_stream(u64::from_le_bytes(stream));
+                rng
+            }
+
+            /// The block counter of the next output.
+            pub fn counter(&self) -> u64 {
+                let next = u64x2::from_bits(self.d).extract(0);
+                next.wrapping_sub(u64::from($blocks - self.idx))
+            }
+
+            /// Restarts output at block `counter` of the current stream.
+            ///
+            /// For RFC 7539, the low 32 bits are its block counter and the
+            /// high 32 bits the first word of its nonce.
+            pub fn set_counter(&mut self, counter: u64) {
+                self.d = u32x4::from_bits(u64x2::from_bits(self.d).replace(0, counter));
+                self.idx = $blocks;
+            }
+
+            /// The stream of the next output.
+            pub fn stream(&self) -> u64 {
+                u64x2::from_bits(self.d).extract(1)
+            }
+
+            /// Switches to `stream`, keeping the block counter.
+            ///
+            /// For RFC 7539, these are the last two words of its nonce.
+            pub fn set_stream(&mut self, stream: u64) {
+                let counter = self.counter();
+                self.d = u32x4::from_bits(u64x2::new(counter, stream));
+                self.idx = $blocks;
+            }
+
+            #[inline(always)]
+            fn blocks(&self) -> [u32x16; $blocks] {
+                let row = |x: u32x4| {
+                    $vector::from_bits($u128xN::splat(counter::load(u64x2::from_bits(x))))
+                };
+                let a0 = row(CHACHA_SEED);
+                let b0 = row(self.b);
+                let c0 = row(self.c);
+                // each block's counter, in the low 64 bits of its row
+                let d0 = $vector::from_bits($u64xN::from_bits(row(self.d)) + $offsets);
+
+                let mut a = a0;
+                let mut b = b0;
+                let mut c = c0;
+                let mut d = d0;
+
+                #[rustfmt::skip]
+                macro_rules! round {
+                    () => {{
+                        a += b; d ^= a; d = d.rotate_left_opt(16);
+                        c += d; b ^= c; b = b.rotate_left_opt(12);
+                        a += b; d ^= a; d = d.rotate_left_opt(8);
+                        c += d; b ^= c; b = b.rotate_left_opt(7);
+                    }};
+                }
+
+                for _double_round in 0..$rounds / 2 {
+                    // columns
+                    round!();
+
+                    // diagonals
+                    b = shuffle!(b, $rot1);
+                    c = shuffle!(c, $rot2);
+                    d = shuffle!(d, $rot3);
+                    round!();
+                    b = shuffle!(b, $rot3);
+                    c = shuffle!(c, $rot2);
+                    d = shuffle!(d, $rot1);
+                }
+
+                a += a0;
+                b += b0;
+                c += c0;
+                d += d0;
+
+                $transpose(a, b, c, d)
+            }
+        }
+
+        impl SeedableRng for $rng_name {
+            type Seed = [u8; 32];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                Self {
+                    b: u32x4::from_bits(u8x16::from_slice_unaligned(&seed[..16])),
+                    c: u32x4::from_bits(u8x16::from_slice_unaligned(&seed[16..])),
+                    d: u32x4::splat(0),
+                    buffer: [u32x16::splat(0); $blocks],
+                    idx: $blocks,
+                }
+            }
+
+            impl_seed_from_u64! {}
+        }
+    };
+}
+
+#[rustfmt::skip]
+macro_rules! make_chacha_x8 {
+    ($rng_name:ident, $rounds:expr) => {
+        make_chacha! {
+            $rng_name, u32x8, u128x2, u64x4, 2, $rounds,
+            u64x4::new(0, 0, 1, 0),
+            [1, 2, 3, 0, 5, 6, 7, 4],
+            [2, 3, 0, 1, 6, 7, 4, 5],
+            [3, 0, 1, 2, 7, 4, 5, 6],
+            transpose_x8
+        }
+    };
+}
+
+#[rustfmt::skip]
+macro_rules! make_chacha_x16 {
+    ($rng_name:ident, $rounds:expr) => {
+        make_chacha! {
+            $rng_name, u32x16, u128x4, u64x8, 4, $rounds,
+            u64x8::new(0, 0, 1, 0, 2, 0, 3, 0),
+            [1, 2, 3, 0, 5, 6, 7, 4, 9, 10, 11, 8, 13, 14, 15, 12],
+            [2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13],
+            [3, 0, 1, 2, 7, 4, 5, 6, 11, 8, 9, 10, 15, 12, 13, 14],
+            transpose_x16
+        }
+    };
+}
+
+make_chacha_x8! { ChaCha8x8, 8 }
+make_chacha_x16! { ChaCha8x16, 8 }
+make_chacha_x8! { ChaCha12x8, 12 }
+make_chacha_x16! { ChaCha12x16, 12 }
+make_chacha_x8! { ChaCha20x8, 20 }
+make_chacha_x16! { ChaCha20x16, 20 }
+
+impl CryptoRng for ChaCha20x8 {}
+impl CryptoRng for ChaCha20x16 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chacha4_at_matches_stream() {
+        let key = [7; 32];
+        let mut rng = ChaCha4::from_seed(key);
+        rng.set_counter(u128::from(u64::max_value()));
+
+        // the block counter carries into the nonce
+        for i in 0..3 {
+            let counter = u128::from(u64::max_value()) + i;
+            assert_eq!(rng.generate(), chacha4_at(key, counter));
+        }
+        assert_eq!(rng.counter(), 1 << 64 | 2);
+    }
+
+    // RFC 7539, section 2.3.2
+    #[test]
+    fn chacha20_rfc7539_block() {
+        let mut key = [0; 32];
+        for (i, k) in key.iter_mut().enumerate() {
+            *k = i as u8;
+        }
+        let mut rng = ChaCha20x8::from_seed(key);
+        // nonce 00:00:00:09:00:00:00:4a:00:00:00:00, block count 1
+        rng.set_counter(0x0900_0000 << 32 | 1);
+        rng.set_stream(0x4a00_0000);
+
+        #[rustfmt::skip]
+        let expected = u32x16::new(
+            0xe4e7f110, 0x15593bd1, 0x1fdd0f50, 0xc47120a3,
+            0xc7f4d1c7, 0x0368c033, 0x9aaa2204, 0x4e6cd4c3,
+            0x466482d2, 0x09aa9f07, 0x05d7c214, 0xa2028bd9,
+            0xd19c12b5, 0xb94e16de, 0xe883d0cb, 0x4e3c50a2,
+        );
+        assert_eq!(rng.generate(), expected);
+    }
+
+    // RFC 7539, appendix A.1, test vector #1
+    #[test]
+    fn chacha20_rfc7539_keystream() {
+        let mut rng = ChaCha20x16::from_seed([0; 32]);
+        let mut bytes = [0; 64];
+        rng.fill_bytes(&mut bytes);
+
+        let expected = [
+            0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90, 0x40, 0x5d, 0x6a, 0xe5, 0x53, 0x86,
+            0xbd, 0x28, 0xbd, 0xd2, 0x19, 0xb8, 0xa0, 0x8d, 0xed, 0x1a, 0xa8, 0x36, 0xef, 0xcc,
+            0x8b, 0x77, 0x0d, 0xc7, 0xda, 0x41, 0x59, 0x7c, 0x51, 0x57, 0x48, 0x8d, 0x77, 0x24,
+            0xe0, 0x3f, 0xb8, 0xd8, 0x4a, 0x37, 0x6a, 0x43, 0xb8, 0xf4, 0x15, 0x18, 0xa1, 0x1c,
+            0xc3, 0x87, 0xb6, 0x69, 0xb2, 0xee, 0x65, 0x86,
+        ];
+        assert_eq!(&bytes[..], &expected[..]);
+    }
+
+    #[test]
+    fn chacha_widths_agree() {
+        let mut x8 = ChaCha8x8::from_seed([3; 32]);
+        let mut x16 = ChaCha8x16::from_seed([3; 32]);
+        x8.set_stream(5);
+        x16.set_stream(5);
+
+        for _ in 0..6 {
+            assert_eq!(x8.generate(), x16.generate());
+        }
+        assert_eq!(x8.counter(), 6);
+        assert_eq!(x16.counter(), 6);
+    }
+
+    // draft-irtf-cfrg-xchacha-03, section 2.2.1
+    #[test]
+    fn hchacha20_subkey() {
+        let mut key = [0; 32];
+        for (i, k) in key.iter_mut().enumerate() {
+            *k = i as u8;
+        }
+        let input = [
+            0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4a, 0x00, 0x00, 0x00, 0x00, 0x31, 0x41,
+            0x59, 0x27,
+        ];
+
+        let expected = [
+            0x82, 0x41, 0x3b, 0x42, 0x27, 0xb2, 0x7b, 0xfe, 0xd3, 0x0e, 0x42, 0x50, 0x8a, 0x87,
+            0x7d, 0x73, 0xa0, 0xf9, 0xe4, 0xd5, 0x8a, 0x74, 0xa8, 0x53, 0xc1, 0x2e, 0xc4, 0x13,
+            0x26, 0xd3, 0xec, 0xdc,
+        ];
+        assert_eq!(hchacha20(key, input), expected);
+    }
+
+    #[test]
+    fn xchacha20_first_block() {
+        let mut key = [0; 32];
+        for (i, k) in key.iter_mut().enumerate() {
+            *k = 0x80 + i as u8;
+        }
+        let mut nonce = [0; 24];
+        for (i, n) in nonce.iter_mut().enumerate() {
+            *n = 0x40 + i as u8;
+        }
+        let mut rng = ChaCha20x16::with_extended_nonce(key, nonce);
+        assert_eq!(rng.stream(), 0x5756_5554_5352_5150);
+
+        #[rustfmt::skip]
+        let expected = u32x16::new(
+            0x801f197b, 0x99f061f3, 0x4b6f4f09, 0xf87db98f,
+            0x7368cc47, 0x90b1f2a8, 0x718073dd, 0xd507f983,
+            0x3827cba1, 0x9f32005b, 0x7012dc7d, 0x2588d659,
+            0xe720a151, 0xe9521363, 0x721538b0, 0x5a1550e9,
+        );
+        assert_eq!(rng.generate(), expected);
+    }
 }
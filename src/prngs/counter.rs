@@ -0,0 +1,13 @@
+//! 128-bit block counters, stored as two `u64` lanes, low lane first.
+
+use rng_impl::*;
+
+#[inline(always)]
+pub fn load(x: u64x2) -> u128 {
+    u128::from(x.extract(0)) | u128::from(x.extract(1)) << 64
+}
+
+#[inline(always)]
+pub fn store(x: u128) -> u64x2 {
+    u64x2::new(x as u64, (x >> 64) as u64)
+}
@@ -1,5 +1,4 @@
-use std::arch::x86_64::*;
-
+use super::widening;
 use rng_impl::*;
 
 /// Intel's `rand_sse` SSE2 LCG
@@ -8,10 +7,15 @@ use rng_impl::*;
 /// - Correlation: unknown
 ///
 /// <https://software.intel.com/en-us/articles/fast-random-number-generator-on-the-intel-pentiumr-4-processor>
+#[derive(Clone, PartialEq, Eq)]
 pub struct IntelLcg {
     cur_seed: u32x4,
 }
 
+impl_debug! { IntelLcg { cur_seed } }
+
+impl_serde! { IntelLcg { cur_seed } }
+
 impl_rngcore! { IntelLcg }
 
 impl SimdRng for IntelLcg {
@@ -25,9 +29,7 @@ impl SimdRng for IntelLcg {
 
         let shuffle = |x: u32x4| shuffle!(x, x, [2, 3, 0, 1]);
         let mul = |x, mul| {
-            u32x4::from_bits(unsafe {
-                _mm_mul_epu32(__m128i::from_bits(x), __m128i::from_bits(mul))
-            })
+            u32x4::from_bits(widening::mul_low32(u64x2::from_bits(x), u64x2::from_bits(mul)))
         };
 
         let mut cur_seed_split = shuffle(self.cur_seed);
@@ -57,4 +59,6 @@ impl SeedableRng for IntelLcg {
 
         Self { cur_seed }
     }
+
+    impl_seed_from_u64! {}
 }
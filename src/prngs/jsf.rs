@@ -1,7 +1,8 @@
 use rng_impl::*;
 
 macro_rules! make_jsf_32 {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             a: $vector,
             b: $vector,
@@ -9,6 +10,10 @@ macro_rules! make_jsf_32 {
             d: $vector,
         }
 
+        impl_debug! { $rng_name { a, b, c, d } }
+
+        impl_serde! { $rng_name { a, b, c, d } }
+
         impl_rngcore! { $rng_name }
 
         impl SimdRng for $rng_name {
@@ -38,15 +43,10 @@ macro_rules! make_jsf_32 {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
-
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 4];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 4);
 
                 let a = seed[0];
                 let b = seed[1];
@@ -76,8 +76,10 @@ macro_rules! make_jsf_32 {
                 d += select_incr!(all_eq!(0x5591F2E3, 0x69EBA6CD, 0x2A171E3D, 0x3FD48890));
                 d += select_incr!(all_eq!(0x47CB8D56, 0xAE9B35A7, 0x5C78F4A8, 0x522240FF));
 
-                Ok(Self { a, b, c, d })
+                Self { a, b, c, d }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -85,14 +87,15 @@ macro_rules! make_jsf_32 {
 // (where `l` is stream length)
 // (using average cycle length)
 // (multiple parameters could be used, though slow on older hardware)
-// Listing probability of overlap somewhere:              Probability
-make_jsf_32! { Jsf32x2,  u32x2  } // ≈ 2^2  * l / 2^127 ≈ l * 2^-125
-make_jsf_32! { Jsf32x4,  u32x4  } // ≈ 4^2  * l / 2^127 ≈ l * 2^-123
-make_jsf_32! { Jsf32x8,  u32x8  } // ≈ 8^2  * l / 2^127 ≈ l * 2^-121
-make_jsf_32! { Jsf32x16, u32x16 } // ≈ 16^2 * l / 2^127 ≈ l * 2^-119
+// Listing probability of overlap somewhere:                        Probability
+make_jsf_32! { Jsf32x2,  u32x2,  [u8; 32] } // ≈ 2^2  * l / 2^127 ≈ l * 2^-125
+make_jsf_32! { Jsf32x4,  u32x4,  Seed512  } // ≈ 4^2  * l / 2^127 ≈ l * 2^-123
+make_jsf_32! { Jsf32x8,  u32x8,  Seed1024 } // ≈ 8^2  * l / 2^127 ≈ l * 2^-121
+make_jsf_32! { Jsf32x16, u32x16, Seed2048 } // ≈ 16^2 * l / 2^127 ≈ l * 2^-119
 
 macro_rules! make_jsf_64 {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             a: $vector,
             b: $vector,
@@ -100,6 +103,10 @@ macro_rules! make_jsf_64 {
             d: $vector,
         }
 
+        impl_debug! { $rng_name { a, b, c, d } }
+
+        impl_serde! { $rng_name { a, b, c, d } }
+
         impl_rngcore! { $rng_name }
 
         impl SimdRng for $rng_name {
@@ -117,15 +124,10 @@ macro_rules! make_jsf_64 {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
-
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 4];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 4);
 
                 let a = seed[0];
                 let b = seed[1];
@@ -136,12 +138,14 @@ macro_rules! make_jsf_64 {
                 let flag = (!(a | b | c | d)).ne($vector::splat(0));
                 d += flag.select($vector::splat(1), $vector::splat(0));
 
-                Ok(Self { a, b, c, d })
+                Self { a, b, c, d }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
 
-make_jsf_64! { Jsf64x2, u64x2 } // ≈ 2^2 * l / 2^255 ≈  l * 2^-253
-make_jsf_64! { Jsf64x4, u64x4 } // ≈ 4^2 * l / 2^255 ≈  l * 2^-251
-make_jsf_64! { Jsf64x8, u64x8 } // ≈ 8^2 * l / 2^255 ≈  l * 2^-249
+make_jsf_64! { Jsf64x2, u64x2, Seed512  } // ≈ 2^2 * l / 2^255 ≈  l * 2^-253
+make_jsf_64! { Jsf64x4, u64x4, Seed1024 } // ≈ 4^2 * l / 2^255 ≈  l * 2^-251
+make_jsf_64! { Jsf64x8, u64x8, Seed2048 } // ≈ 8^2 * l / 2^255 ≈  l * 2^-249
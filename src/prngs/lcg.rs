@@ -4,12 +4,19 @@
 use rng_impl::*;
 
 macro_rules! make_lcg {
-    ($rng_name:ident, $vector:ident, $half:ident, $mul:expr) => {
+    ($rng_name:ident, $vector:ident, $half:ident, $seed:ty, $mul:expr) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             state: $vector,
             inc: $vector,
         }
 
+        impl_debug! { $rng_name { state, inc } }
+
+        impl_serde! { $rng_name { state, inc } }
+
+        impl_lcg_advance! { $rng_name, $vector, $mul }
+
         impl_rngcore! { $rng_name }
 
         impl SimdRng for $rng_name {
@@ -26,15 +33,10 @@ macro_rules! make_lcg {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
-
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 2];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 2);
 
                 let mut lcg = Self {
                     state: seed[0],
@@ -43,31 +45,60 @@ macro_rules! make_lcg {
 
                 lcg.state = lcg.state * $mul + lcg.inc;
 
-                Ok(lcg)
+                lcg
             }
+
+            impl_seed_from_u64! {}
         }
     };
 
-    ( 32_bit_out: $rng_name:ident, $vector:ident, $half:ident) => {
-        make_lcg! { $rng_name, $vector, $half, 6364136223846793005 }
+    ( 32_bit_out: $rng_name:ident, $vector:ident, $half:ident, $seed:ty) => {
+        make_lcg! { $rng_name, $vector, $half, $seed, 6364136223846793005 }
     };
 
     // 64-bit SIMD multiplication is less supported than 32-bit. These PRNGs
     // might be faster
-    ( 16_bit_out: $rng_name:ident, $vector:ident, $half:ident) => {
-        make_lcg! { $rng_name, $vector, $half, 747796405 }
+    ( 16_bit_out: $rng_name:ident, $vector:ident, $half:ident, $seed:ty) => {
+        make_lcg! { $rng_name, $vector, $half, $seed, 747796405 }
     };
 }
 
 // (where `l` is stream length)
 // (multiple parameters could be used)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:            Probability
-make_lcg! { 32_bit_out: Lcg32x2,  u64x2,  u32x2  } // ≈ 2^2  * l / 2^64 ≈ l * 2^-62
-make_lcg! { 32_bit_out: Lcg32x4,  u64x4,  u32x4  } // ≈ 4^2  * l / 2^64 ≈ l * 2^-60
-make_lcg! { 32_bit_out: Lcg32x8,  u64x8,  u32x8  } // ≈ 8^2  * l / 2^64 ≈ l * 2^-58
-
-make_lcg! { 16_bit_out: Lcg16x2,  u32x2,  u16x2  } // ≈ 2^2  * l / 2^32 ≈ l * 2^-30
-make_lcg! { 16_bit_out: Lcg16x4,  u32x4,  u16x4  } // ≈ 4^2  * l / 2^32 ≈ l * 2^-28
-make_lcg! { 16_bit_out: Lcg16x8,  u32x8,  u16x8  } // ≈ 8^2  * l / 2^32 ≈ l * 2^-26
-make_lcg! { 16_bit_out: Lcg16x16, u32x16, u16x16 } // ≈ 16^2 * l / 2^32 ≈ l * 2^-24
+// Listing probability of overlap somewhere:                      Probability
+make_lcg! { 32_bit_out: Lcg32x2,  u64x2,  u32x2,  [u8; 32] } // ≈ 2^2  * l / 2^64 ≈ l * 2^-62
+make_lcg! { 32_bit_out: Lcg32x4,  u64x4,  u32x4,  Seed512  } // ≈ 4^2  * l / 2^64 ≈ l * 2^-60
+make_lcg! { 32_bit_out: Lcg32x8,  u64x8,  u32x8,  Seed1024 } // ≈ 8^2  * l / 2^64 ≈ l * 2^-58
+
+make_lcg! { 16_bit_out: Lcg16x2,  u32x2,  u16x2,  [u8; 16] } // ≈ 2^2  * l / 2^32 ≈ l * 2^-30
+make_lcg! { 16_bit_out: Lcg16x4,  u32x4,  u16x4,  [u8; 32] } // ≈ 4^2  * l / 2^32 ≈ l * 2^-28
+make_lcg! { 16_bit_out: Lcg16x8,  u32x8,  u16x8,  Seed512  } // ≈ 8^2  * l / 2^32 ≈ l * 2^-26
+make_lcg! { 16_bit_out: Lcg16x16, u32x16, u16x16, Seed1024 } // ≈ 16^2 * l / 2^32 ≈ l * 2^-24
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_matches_generate() {
+        let mut rng = Lcg32x4::seed_from_u64(0);
+        let mut jumped = rng.clone();
+        for _ in 0..1000 {
+            rng.generate();
+        }
+        jumped.advance(1000);
+        assert_eq!(rng, jumped);
+
+        let mut lanes = Lcg16x4::seed_from_u64(0);
+        let expected = lanes.clone();
+        lanes.advance_lanes(u32x4::new(0, 1, 2, 3));
+        for i in 0..4 {
+            let mut lane = expected.clone();
+            for _ in 0..i {
+                lane.generate();
+            }
+            assert_eq!(lanes.state.extract(i), lane.state.extract(i));
+        }
+    }
+}
@@ -2,11 +2,48 @@
 //!
 //! They allow jumping so might be good for avoiding correlations
 
+use std::mem;
+use std::ops::BitXorAssign;
+
 use rng_impl::*;
 
+/// Jump polynomials: `x^(2^64)` (lfsr113) and `x^(2^128)` (lfsr258) modulo
+/// the minimal polynomial of each component's step, on the full word.
+///
+/// Components with short periods jump only a few steps: 2^64 is 4 modulo
+/// 2^31 - 1, for example.
+const JUMP_113: [u32; 4] = [0x10, 0x440, 0x171679ba, 0x1268bc2];
+const JUMP_258: [u64; 5] = [
+    0x10,
+    0xd7fb3125fac896,
+    0x1e88cf5fd51a2e,
+    0x5f36f89389ba,
+    0x100000000,
+];
+
+/// Jumps one component: `poly` evaluated at its `step`, on whole vectors.
+/// A polynomial word covers as many steps as it has bits.
+#[inline(always)]
+fn jump_component<V, W>(mut z: V, poly: W, step: fn(V) -> V) -> V
+where
+    V: Copy + Default + BitXorAssign,
+    W: Into<u64>,
+{
+    let poly = poly.into();
+    let mut acc = V::default();
+    for b in 0..8 * mem::size_of::<W>() {
+        if poly >> b & 1 != 0 {
+            acc ^= z;
+        }
+        z = step(z);
+    }
+    acc
+}
+
 macro_rules! make_lfsr113 {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
         /// Period: 2^113
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             z1: $vector,
             z2: $vector,
@@ -14,8 +51,72 @@ macro_rules! make_lfsr113 {
             z4: $vector,
         }
 
+        impl_debug! { $rng_name { z1, z2, z3, z4 } }
+
+        impl_serde! {
+            $rng_name { z1, z2, z3, z4 } where |rng| {
+                let below = |z: $vector, min| z.lt($vector::splat(min)).any();
+                if below(rng.z1, 2) || below(rng.z2, 8) || below(rng.z3, 16) || below(rng.z4, 128) {
+                    Err("a component below its lower bound")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
         impl_rngcore! { $rng_name }
 
+        impl SplittableRng for $rng_name {
+            fn split(&mut self) -> Self {
+                reseed(self)
+            }
+        }
+
+        impl $rng_name {
+            pub fn blocks_from_rng<R: Rng>(rng: R) -> Result<Self, Error> {
+                let seed = Self::from_rng(rng)?;
+
+                let mut scalar = Self {
+                    z1: $vector::splat(seed.z1.extract(0)),
+                    z2: $vector::splat(seed.z2.extract(0)),
+                    z3: $vector::splat(seed.z3.extract(0)),
+                    z4: $vector::splat(seed.z4.extract(0)),
+                };
+                let mut blocks = scalar.clone();
+
+                for i in 1..$vector::lanes() {
+                    // Each stream has 2^64 values before it begins to repeat
+                    // the next stream (except the last stream).
+                    scalar.jump();
+                    blocks.z1 = blocks.z1.replace(i, scalar.z1.extract(0));
+                    blocks.z2 = blocks.z2.replace(i, scalar.z2.extract(0));
+                    blocks.z3 = blocks.z3.replace(i, scalar.z3.extract(0));
+                    blocks.z4 = blocks.z4.replace(i, scalar.z4.extract(0));
+                }
+
+                Ok(blocks)
+            }
+
+            /// Advances every lane by 2^64 steps.
+            ///
+            /// Each component is jumped with its own polynomial, on whole
+            /// vectors.
+            pub fn jump(&mut self) {
+                self.z1 = jump_component(self.z1, JUMP_113[0], |z| {
+                    ((z & 4294967294) << 18) ^ (((z << 6) ^ z) >> 13)
+                });
+                self.z2 = jump_component(self.z2, JUMP_113[1], |z| {
+                    ((z & 4294967288) << 2) ^ (((z << 2) ^ z) >> 27)
+                });
+                self.z3 = jump_component(self.z3, JUMP_113[2], |z| {
+                    ((z & 4294967280) << 7) ^ (((z << 13) ^ z) >> 21)
+                });
+                self.z4 = jump_component(self.z4, JUMP_113[3], |z| {
+                    ((z & 4294967168) << 13) ^ (((z << 3) ^ z) >> 12)
+                });
+            }
+        }
+
         impl SimdRng for $rng_name {
             type Result = $vector;
 
@@ -35,37 +136,26 @@ macro_rules! make_lfsr113 {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 4);
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 4];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+                // Each component has a lower bound, lanes below it are
+                // replaced with a different value per lane and component
+                let lower_bound = |z: $vector, min, key| {
+                    z.lt($vector::splat(min)).select(lane_fill!($vector, key) | min, z)
+                };
 
-                // could perhaps use the seeding of ISPC
-                while seed[0].le($vector::splat(1)).any() {
-                    rng.try_fill_bytes(seed[0..=0].as_byte_slice_mut())?;
+                Self {
+                    z1: lower_bound(seed[0], 2, 0),
+                    z2: lower_bound(seed[1], 8, 1),
+                    z3: lower_bound(seed[2], 16, 2),
+                    z4: lower_bound(seed[3], 128, 3),
                 }
-                while seed[1].le($vector::splat(7)).any() {
-                    rng.try_fill_bytes(seed[1..=1].as_byte_slice_mut())?;
-                }
-                while seed[2].le($vector::splat(15)).any() {
-                    rng.try_fill_bytes(seed[2..=2].as_byte_slice_mut())?;
-                }
-                while seed[3].le($vector::splat(127)).any() {
-                    rng.try_fill_bytes(seed[3..=3].as_byte_slice_mut())?;
-                }
-
-                Ok(Self {
-                    z1: seed[0],
-                    z2: seed[1],
-                    z3: seed[2],
-                    z4: seed[3],
-                })
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -74,15 +164,16 @@ macro_rules! make_lfsr113 {
 // (multiple parameters could be used, though slow on older hardware)
 // (jumping is possible)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                 Probability
-make_lfsr113! { Lfsr113x2,  u32x2  } // ≈ 2^2  * l / 2^113 ≈ l * 2^-111
-make_lfsr113! { Lfsr113x4,  u32x4  } // ≈ 4^2  * l / 2^113 ≈ l * 2^-109
-make_lfsr113! { Lfsr113x8,  u32x8  } // ≈ 8^2  * l / 2^113 ≈ l * 2^-107
-make_lfsr113! { Lfsr113x16, u32x16 } // ≈ 16^2 * l / 2^113 ≈ l * 2^-105
+// Listing probability of overlap somewhere:                           Probability
+make_lfsr113! { Lfsr113x2,  u32x2,  [u8; 32] } // ≈ 2^2  * l / 2^113 ≈ l * 2^-111
+make_lfsr113! { Lfsr113x4,  u32x4,  Seed512  } // ≈ 4^2  * l / 2^113 ≈ l * 2^-109
+make_lfsr113! { Lfsr113x8,  u32x8,  Seed1024 } // ≈ 8^2  * l / 2^113 ≈ l * 2^-107
+make_lfsr113! { Lfsr113x16, u32x16, Seed2048 } // ≈ 16^2 * l / 2^113 ≈ l * 2^-105
 
 macro_rules! make_lfsr258 {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
         /// Period: 2^258
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             y1: $vector,
             y2: $vector,
@@ -91,8 +182,82 @@ macro_rules! make_lfsr258 {
             y5: $vector,
         }
 
+        impl_debug! { $rng_name { y1, y2, y3, y4, y5 } }
+
+        impl_serde! {
+            $rng_name { y1, y2, y3, y4, y5 } where |rng| {
+                let below = |z: $vector, min| z.lt($vector::splat(min)).any();
+                if below(rng.y1, 2)
+                    || below(rng.y2, 512)
+                    || below(rng.y3, 4096)
+                    || below(rng.y4, 131072)
+                    || below(rng.y5, 8388608)
+                {
+                    Err("a component below its lower bound")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
         impl_rngcore! { $rng_name }
 
+        impl SplittableRng for $rng_name {
+            fn split(&mut self) -> Self {
+                reseed(self)
+            }
+        }
+
+        impl $rng_name {
+            pub fn blocks_from_rng<R: Rng>(rng: R) -> Result<Self, Error> {
+                let seed = Self::from_rng(rng)?;
+
+                let mut scalar = Self {
+                    y1: $vector::splat(seed.y1.extract(0)),
+                    y2: $vector::splat(seed.y2.extract(0)),
+                    y3: $vector::splat(seed.y3.extract(0)),
+                    y4: $vector::splat(seed.y4.extract(0)),
+                    y5: $vector::splat(seed.y5.extract(0)),
+                };
+                let mut blocks = scalar.clone();
+
+                for i in 1..$vector::lanes() {
+                    // Each stream has 2^128 values before it begins to repeat
+                    // the next stream (except the last stream).
+                    scalar.jump();
+                    blocks.y1 = blocks.y1.replace(i, scalar.y1.extract(0));
+                    blocks.y2 = blocks.y2.replace(i, scalar.y2.extract(0));
+                    blocks.y3 = blocks.y3.replace(i, scalar.y3.extract(0));
+                    blocks.y4 = blocks.y4.replace(i, scalar.y4.extract(0));
+                    blocks.y5 = blocks.y5.replace(i, scalar.y5.extract(0));
+                }
+
+                Ok(blocks)
+            }
+
+            /// Advances every lane by 2^128 steps.
+            ///
+            /// Each component is jumped with its own polynomial, on whole
+            /// vectors.
+            pub fn jump(&mut self) {
+                self.y1 = jump_component(self.y1, JUMP_258[0], |y| {
+                    ((y & 18446744073709551614) << 10) ^ (((y << 1) ^ y) >> 53)
+                });
+                self.y2 = jump_component(self.y2, JUMP_258[1], |y| {
+                    ((y & 18446744073709551104) << 5) ^ (((y << 24) ^ y) >> 50)
+                });
+                self.y3 = jump_component(self.y3, JUMP_258[2], |y| {
+                    ((y & 18446744073709547520) << 29) ^ (((y << 3) ^ y) >> 23)
+                });
+                self.y4 = jump_component(self.y4, JUMP_258[3], |y| {
+                    ((y & 18446744073709420544) << 23) ^ (((y << 5) ^ y) >> 24)
+                });
+                self.y5 = jump_component(self.y5, JUMP_258[4], |y| {
+                    ((y & 18446744073701163008) << 8) ^ (((y << 3) ^ y) >> 33)
+                });
+            }
+        }
+
         impl SimdRng for $rng_name {
             type Result = $vector;
 
@@ -115,40 +280,27 @@ macro_rules! make_lfsr258 {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 5);
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 5];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+                // Each component has a lower bound, lanes below it are
+                // replaced with a different value per lane and component
+                let lower_bound = |z: $vector, min, key| {
+                    z.lt($vector::splat(min)).select(lane_fill!($vector, key) | min, z)
+                };
 
-                while seed[0].le($vector::splat(1)).any() {
-                    rng.try_fill_bytes(seed[0..=0].as_byte_slice_mut())?;
-                }
-                while seed[1].le($vector::splat(511)).any() {
-                    rng.try_fill_bytes(seed[1..=1].as_byte_slice_mut())?;
-                }
-                while seed[2].le($vector::splat(4095)).any() {
-                    rng.try_fill_bytes(seed[2..=2].as_byte_slice_mut())?;
-                }
-                while seed[3].le($vector::splat(131071)).any() {
-                    rng.try_fill_bytes(seed[3..=3].as_byte_slice_mut())?;
-                }
-                while seed[4].le($vector::splat(8388607)).any() {
-                    rng.try_fill_bytes(seed[4..=4].as_byte_slice_mut())?;
+                Self {
+                    y1: lower_bound(seed[0], 2, 0),
+                    y2: lower_bound(seed[1], 512, 1),
+                    y3: lower_bound(seed[2], 4096, 2),
+                    y4: lower_bound(seed[3], 131072, 3),
+                    y5: lower_bound(seed[4], 8388608, 4),
                 }
-
-                Ok(Self {
-                    y1: seed[0],
-                    y2: seed[1],
-                    y3: seed[2],
-                    y4: seed[3],
-                    y5: seed[4],
-                })
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -156,7 +308,108 @@ macro_rules! make_lfsr258 {
 // (where `l` is stream length)
 // (multiple parameters could be used, though slow on older hardware)
 // (jumping is possible)
-// Listing probability of overlap somewhere:              Probability
-make_lfsr258! { Lfsr258x2, u64x2 } // ≈ 2^2 * l / 2^258 ≈ l * 2^-256
-make_lfsr258! { Lfsr258x4, u64x4 } // ≈ 4^2 * l / 2^258 ≈ l * 2^-254
-make_lfsr258! { Lfsr258x8, u64x8 } // ≈ 8^2 * l / 2^258 ≈ l * 2^-252
+// Listing probability of overlap somewhere:                        Probability
+make_lfsr258! { Lfsr258x2, u64x2, Seed640  } // ≈ 2^2 * l / 2^258 ≈ l * 2^-256
+make_lfsr258! { Lfsr258x4, u64x4, Seed1280 } // ≈ 4^2 * l / 2^258 ≈ l * 2^-254
+make_lfsr258! { Lfsr258x8, u64x8, Seed2560 } // ≈ 8^2 * l / 2^258 ≈ l * 2^-252
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_seed_lanes_differ() {
+        let rng = Lfsr113x4::from_seed(Default::default());
+        assert!(rng.z4.ge(u32x4::splat(128)).all());
+        for i in 1..4 {
+            assert_ne!(rng.z1.extract(i), rng.z1.extract(0));
+        }
+    }
+
+    #[test]
+    fn jump_short_components() {
+        // the first components' jumps reduce to a few steps, see `JUMP_113`
+        let mut rng = Lfsr113x4::seed_from_u64(0);
+        let mut jumped = rng.clone();
+        jumped.jump();
+        for _ in 0..4 {
+            rng.generate();
+        }
+        assert_eq!(jumped.z1, rng.z1);
+
+        let mut rng = Lfsr258x2::seed_from_u64(0);
+        let mut jumped = rng.clone();
+        jumped.jump();
+        for _ in 0..32 {
+            rng.generate();
+        }
+        assert_eq!(jumped.y5, rng.y5);
+    }
+
+    /// Applies a matrix over GF(2), given as columns, to `v`.
+    fn apply(cols: &[u64], v: u64) -> u64 {
+        cols.iter().enumerate().filter(|&(b, _)| v >> b & 1 != 0).fold(0, |acc, (_, c)| acc ^ c)
+    }
+
+    /// Squares a matrix `e` times, raising it to `2^e`.
+    fn power(mut cols: Vec<u64>, e: usize) -> Vec<u64> {
+        for _ in 0..e {
+            cols = cols.iter().map(|&c| apply(&cols, c)).collect();
+        }
+        cols
+    }
+
+    macro_rules! test_jump {
+        ($fnn:ident, $rng:ident, $vector:ident, $bits:expr, $log_distance:expr, $($z:ident),+) => {
+            #[test]
+            fn $fnn() {
+                let lanes = |z: $vector| -> Vec<u64> {
+                    (0..$vector::lanes()).map(|i| z.extract(i) as u64).collect()
+                };
+                let components = |rng: &$rng| vec![$(lanes(rng.$z)),+];
+                // Check each component of `to` is `matrices` applied to `from`,
+                // lane by lane, or to the previous lane with `shift`
+                let check = |matrices: &[Vec<u64>], from: &$rng, to: &$rng, shift| {
+                    let (from, to) = (components(from), components(to));
+                    for (m, (f, t)) in matrices.iter().zip(from.iter().zip(&to)) {
+                        for (&f, &t) in f.iter().zip(&t[shift..]) {
+                            assert_eq!(apply(m, f), t);
+                        }
+                    }
+                };
+
+                // Each component's step matrix, column by column from
+                // stepping unit vectors
+                let mut steps = vec![vec![]; components(&$rng::seed_from_u64(0)).len()];
+                for b in 0..$bits {
+                    let mut unit = $rng { $($z: $vector::splat(1 << b)),+ };
+                    unit.generate();
+                    for (m, c) in steps.iter_mut().zip(components(&unit)) {
+                        m.push(c[0]);
+                    }
+                }
+
+                // The matrices agree with 2^10 steps of the generator
+                let start = $rng::seed_from_u64(0);
+                let mut stepped = start.clone();
+                for _ in 0..1 << 10 {
+                    stepped.generate();
+                }
+                let small: Vec<_> = steps.iter().map(|m| power(m.clone(), 10)).collect();
+                check(&small, &start, &stepped, 0);
+
+                // `jump` and `blocks_from_rng` advance by the full distance
+                let full: Vec<_> = steps.iter().map(|m| power(m.clone(), $log_distance)).collect();
+                let mut jumped = start.clone();
+                jumped.jump();
+                check(&full, &start, &jumped, 0);
+
+                let blocks = $rng::blocks_from_rng(SplitMix64::new(0)).unwrap();
+                check(&full, &blocks, &blocks, 1);
+            }
+        };
+    }
+
+    test_jump! { jump_lfsr113, Lfsr113x4, u32x4, 32, 64, z1, z2, z3, z4 }
+    test_jump! { jump_lfsr258, Lfsr258x4, u64x4, 64, 128, y1, y2, y3, y4, y5 }
+}
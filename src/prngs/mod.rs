@@ -1,3 +1,7 @@
+mod aes;
+mod counter;
+mod widening;
+
 mod ars;
 pub use self::ars::*;
 
@@ -45,6 +49,18 @@ pub use self::pcg_fixed::*;
 mod lfsr;
 pub use self::lfsr::*;
 
+mod philox;
+pub use self::philox::*;
+
+mod threefry;
+pub use self::threefry::*;
+
+mod mrg32k3a;
+pub use self::mrg32k3a::*;
+
+mod mrg8;
+pub use self::mrg8::*;
+
 mod mwc;
 pub use self::mwc::*;
 
@@ -57,5 +73,11 @@ pub use self::intel_lcg::*;
 mod chacha;
 pub use self::chacha::*;
 
+mod salsa;
+pub use self::salsa::*;
+
+mod aes_ctr;
+pub use self::aes_ctr::*;
+
 mod aes_rand;
 pub use self::aes_rand::*;
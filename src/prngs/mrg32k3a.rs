@@ -0,0 +1,302 @@
+// Each lane is its own stream of L'Ecuyer's RngStreams package. The
+// reference computes in doubles, this uses 64-bit integer lanes instead.
+// Every intermediate is an exact integer either way, so the states and the
+// outputs are the same bit for bit.
+
+use rand::AsByteSliceMut as RandAsByteSliceMut;
+use rng_impl::*;
+
+const M1: u64 = 4294967087;
+const M2: u64 = 4294944443;
+const A12: u64 = 1403580;
+const A13N: u64 = 810728;
+const A21: u64 = 527612;
+const A23N: u64 = 1370589;
+
+/// `1 / (M1 + 1)`, as in the reference.
+const NORM: f64 = 2.328306549295728e-10;
+
+/// Jump matrices for each component, by 2^76 steps (a substream) and 2^127
+/// steps (a stream).
+///
+/// - Source: <https://github.com/umontreal-simul/RngStreams/blob/master/c/RngStream.c>
+#[rustfmt::skip]
+const A1P76: [[u64; 3]; 3] = [
+    [  82758667, 1871391091, 4127413238],
+    [3672831523,   69195019, 1871391091],
+    [3672091415, 3528743235,   69195019],
+];
+#[rustfmt::skip]
+const A2P76: [[u64; 3]; 3] = [
+    [1511326704, 3759209742, 1610795712],
+    [4292754251, 1511326704, 3889917532],
+    [3859662829, 4292754251, 3708466080],
+];
+#[rustfmt::skip]
+const A1P127: [[u64; 3]; 3] = [
+    [2427906178, 3580155704,  949770784],
+    [ 226153695, 1230515664, 3580155704],
+    [1988835001,  986791581, 1230515664],
+];
+#[rustfmt::skip]
+const A2P127: [[u64; 3]; 3] = [
+    [1464411153,  277697599, 1610723613],
+    [  32183930, 1464411153, 1022607788],
+    [2824425944,   32183930, 2093834863],
+];
+
+type State = [[u64; 3]; 2];
+
+/// `a * s mod m`, as the reference's `MatVecModM`.
+fn mat_vec_mod(a: &[[u64; 3]; 3], s: [u64; 3], m: u64) -> [u64; 3] {
+    let mut x = [0; 3];
+    for (x, row) in x.iter_mut().zip(a) {
+        let sum: u128 = row
+            .iter()
+            .zip(&s)
+            .map(|(&a, &s)| u128::from(a) * u128::from(s))
+            .sum();
+        *x = (sum % u128::from(m)) as u64;
+    }
+    x
+}
+
+fn jump(s: State, a1: &[[u64; 3]; 3], a2: &[[u64; 3]; 3]) -> State {
+    [mat_vec_mod(a1, s[0], M1), mat_vec_mod(a2, s[1], M2)]
+}
+
+macro_rules! make_mrg32k3a {
+    ($rng_name:ident, $vector:ident, $result:ident, $float:ident) => {
+        /// MRG32k3a, one RngStreams stream per lane.
+        ///
+        /// - Source: L'Ecuyer et al., "An Object-Oriented Random-Number
+        ///   Package with Many Long Streams and Substreams" (2002)
+        /// - State: 192-bits per lane
+        /// - Output: integers in `1..=4294967087`, 32-bits per lane
+        /// - Cycle Length: ≈ 2<sup>191</sup>
+        /// - BigCrush: passed
+        ///
+        /// Outputs are `RngStream_RandU01` before scaling, see
+        /// `generate_f64` for the scaled ones.
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $rng_name {
+            state: [[$vector; 3]; 2],
+            substream: [[$vector; 3]; 2],
+            stream: [[$vector; 3]; 2],
+        }
+
+        impl_debug! { $rng_name { state, substream, stream } }
+
+        impl_serde! { $rng_name { state, substream, stream } }
+
+        impl SimdRng for $rng_name {
+            type Result = $result;
+
+            #[inline(always)]
+            fn generate(&mut self) -> $result {
+                self.step().cast()
+            }
+        }
+
+        impl_rngcore! { $rng_name }
+
+        impl $rng_name {
+            /// Lane `i` is the `i`th stream RngStreams creates after
+            /// `RngStream_SetPackageSeed(seed)`. The reference's default seed
+            /// is `[12345; 6]`.
+            ///
+            /// The first three words must be below 4294967087, the last three
+            /// below 4294944443, and neither three all zero.
+            pub fn from_package_seed(seed: [u32; 6]) -> Self {
+                assert!(seed[..3].iter().all(|&x| u64::from(x) < M1));
+                assert!(seed[3..].iter().all(|&x| u64::from(x) < M2));
+                assert!(seed[..3].iter().any(|&x| x != 0));
+                assert!(seed[3..].iter().any(|&x| x != 0));
+
+                let mut next = [[0; 3]; 2];
+                for (i, &x) in seed.iter().enumerate() {
+                    next[i / 3][i % 3] = u64::from(x);
+                }
+
+                let mut stream = [[$vector::splat(0); 3]; 2];
+                for lane in 0..$vector::lanes() {
+                    for (s, n) in stream.iter_mut().zip(&next) {
+                        for (s, &n) in s.iter_mut().zip(n) {
+                            *s = s.replace(lane, n);
+                        }
+                    }
+                    next = jump(next, &A1P127, &A2P127);
+                }
+
+                Self {
+                    state: stream,
+                    substream: stream,
+                    stream,
+                }
+            }
+
+            /// Moves every lane to the start of its next substream, as
+            /// `RngStream_ResetNextSubstream`.
+            pub fn next_substream(&mut self) {
+                self.substream = Self::jump_lanes(self.substream, &A1P76, &A2P76, 1);
+                self.state = self.substream;
+            }
+
+            /// Moves every lane `lanes()` streams on, to the streams the
+            /// package would create after these.
+            pub fn next_stream(&mut self) {
+                let lanes = $vector::lanes();
+                self.stream = Self::jump_lanes(self.stream, &A1P127, &A2P127, lanes);
+                self.substream = self.stream;
+                self.state = self.stream;
+            }
+
+            /// As `RngStream_ResetStartStream`.
+            pub fn reset_start_stream(&mut self) {
+                self.substream = self.stream;
+                self.state = self.stream;
+            }
+
+            /// As `RngStream_ResetStartSubstream`.
+            pub fn reset_start_substream(&mut self) {
+                self.state = self.substream;
+            }
+
+            /// The next outputs scaled to `(0, 1)`, bit for bit
+            /// `RngStream_RandU01` without antithetic or increased precision.
+            pub fn generate_f64(&mut self) -> $float {
+                let z: $float = self.step().cast();
+                z * NORM
+            }
+
+            fn jump_lanes(
+                s: [[$vector; 3]; 2],
+                a1: &[[u64; 3]; 3],
+                a2: &[[u64; 3]; 3],
+                times: usize,
+            ) -> [[$vector; 3]; 2] {
+                let mut jumped = s;
+                for lane in 0..$vector::lanes() {
+                    let mut x = [[0; 3]; 2];
+                    for (x, s) in x.iter_mut().zip(&s) {
+                        for (x, s) in x.iter_mut().zip(s) {
+                            *x = s.extract(lane);
+                        }
+                    }
+
+                    for _ in 0..times {
+                        x = jump(x, a1, a2);
+                    }
+
+                    for (j, x) in jumped.iter_mut().zip(&x) {
+                        for (j, &x) in j.iter_mut().zip(x) {
+                            *j = j.replace(lane, x);
+                        }
+                    }
+                }
+                jumped
+            }
+
+            /// `x mod m` for `x` below 2^54, folding the high half with
+            /// `2^32 ≡ 2^32 - m`.
+            #[inline(always)]
+            fn reduce(mut x: $vector, m: u64) -> $vector {
+                let c = (1 << 32) - m;
+                for _ in 0..3 {
+                    x = (x >> 32) * c + (x & 0xffff_ffff);
+                }
+                x.ge($vector::splat(m)).select(x - m, x)
+            }
+
+            #[inline(always)]
+            fn step(&mut self) -> $vector {
+                let [s1, s2] = &mut self.state;
+
+                // the subtracted terms are made positive mod m
+                let p1 = Self::reduce(s1[1] * A12 + ($vector::splat(M1) - s1[0]) * A13N, M1);
+                *s1 = [s1[1], s1[2], p1];
+
+                let p2 = Self::reduce(s2[2] * A21 + ($vector::splat(M2) - s2[0]) * A23N, M2);
+                *s2 = [s2[1], s2[2], p2];
+
+                p1.gt(p2).select(p1 - p2, p1 + M1 - p2)
+            }
+        }
+
+        impl SeedableRng for $rng_name {
+            type Seed = [u8; 24];
+
+            /// Reads a package seed, made valid, see `from_package_seed`.
+            fn from_seed(seed: Self::Seed) -> Self {
+                let mut package = [0_u32; 6];
+                package.as_byte_slice_mut().copy_from_slice(&seed);
+                for (i, x) in package.iter_mut().enumerate() {
+                    let m = if i < 3 { M1 } else { M2 };
+                    *x = u32::from_le(*x);
+                    if u64::from(*x) >= m {
+                        *x -= m as u32;
+                    }
+                }
+
+                for component in package.chunks_mut(3) {
+                    if component.iter().all(|&x| x == 0) {
+                        component[0] = 12345;
+                    }
+                }
+
+                Self::from_package_seed(package)
+            }
+
+            impl_seed_from_u64! {}
+        }
+    };
+}
+
+make_mrg32k3a! { Mrg32k3aX2, u64x2, u32x2, f64x2 }
+make_mrg32k3a! { Mrg32k3aX4, u64x4, u32x4, f64x4 }
+make_mrg32k3a! { Mrg32k3aX8, u64x8, u32x8, f64x8 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_rng_streams() {
+        let mut rng = Mrg32k3aX4::from_package_seed([12345; 6]);
+
+        // the first stream, then the second
+        let expected = [
+            [545508589, 3262379099],
+            [1368065410, 4201811714],
+            [1327943761, 2942635747],
+        ];
+        for e in &expected {
+            let x = rng.generate();
+            assert_eq!([x.extract(0), x.extract(1)], *e);
+        }
+
+        rng.reset_start_stream();
+        let u = rng.generate_f64().extract(0);
+        assert_eq!(u.to_bits(), 0.12701112204657714_f64.to_bits());
+
+        // the first stream's second substream
+        rng.next_substream();
+        for &e in &[341016048, 2063042364, 3686465802] {
+            assert_eq!(rng.generate().extract(0), e);
+        }
+    }
+
+    #[test]
+    fn next_stream_continues_the_package() {
+        let mut rng = Mrg32k3aX2::from_package_seed([12345; 6]);
+        let wide = Mrg32k3aX4::from_package_seed([12345; 6]);
+        rng.next_stream();
+
+        for (c, w) in rng.state.iter().zip(&wide.state) {
+            for (c, w) in c.iter().zip(w) {
+                assert_eq!(c.extract(0), w.extract(2));
+                assert_eq!(c.extract(1), w.extract(3));
+            }
+        }
+    }
+}
@@ -0,0 +1,253 @@
+// A single stream, vectorized by stepping 8 values at once: the next block
+// is the last one times the 8-step transition matrix, computed as a sum of
+// broadcast lanes times columns.
+
+use rand::AsByteSliceMut as RandAsByteSliceMut;
+use rng_impl::*;
+
+/// 2^31 - 1
+const P: u64 = 0x7fff_ffff;
+
+/// `x[n] = A[0] x[n - 1] + ... + A[7] x[n - 8] mod P`, from the paper.
+const A: [u64; 8] = [
+    1089656042, 1906537547, 1764115693, 1304127872, 189748160, 1984088114, 626062218, 1927846343,
+];
+
+/// Columns of the 8-step transition matrix, the companion matrix of `A` to
+/// the 8th power.
+#[rustfmt::skip]
+const BLOCK: [u64x8; 8] = [
+    u64x8::new(1927846343,    6399920, 1933500836, 1056250092,  453325195, 1016341956, 1178012909,  703544845),
+    u64x8::new( 626062218, 1677247183,  909018788,  537379383, 2131737181, 1631992682, 1560996184,  396650427),
+    u64x8::new(1984088114,  124459089,  107007659, 1789239196, 1313071549, 1064124309,  980603960,  858514955),
+    u64x8::new( 189748160,  794653789,  210234543, 1154141956, 1298480670, 1635454520,  154069568, 1178509058),
+    u64x8::new(1304127872,  778891040, 1659045147, 1996406371,  673321687,  485668455,  189427394, 1752306892),
+    u64x8::new(1764115693,  959400923,  270060787, 1598299827, 1233163376,  194315559,  667498423, 2048627484),
+    u64x8::new(1906537547,  225181104, 1055914432,  674246990,  412745950, 1290106091, 1147349621,  482623495),
+    u64x8::new(1089656042, 1309965256,  223682815, 1709517429,  689948733,  536966461, 1731761482, 1309738347),
+];
+
+type Matrix = [[u64; 8]; 8];
+
+/// The one-step transition matrix, on states ordered oldest first.
+fn companion() -> Matrix {
+    let mut m = [[0; 8]; 8];
+    for (i, row) in m.iter_mut().enumerate().take(7) {
+        row[i + 1] = 1;
+    }
+    for (k, &a) in A.iter().enumerate() {
+        m[7][7 - k] = a;
+    }
+    m
+}
+
+fn mat_mul(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut m = [[0; 8]; 8];
+    for (row, a) in m.iter_mut().zip(a) {
+        for (j, x) in row.iter_mut().enumerate() {
+            let sum: u128 = a
+                .iter()
+                .zip(b)
+                .map(|(&a, b)| u128::from(a) * u128::from(b[j]))
+                .sum();
+            *x = (sum % u128::from(P)) as u64;
+        }
+    }
+    m
+}
+
+fn mat_vec(a: &Matrix, v: [u64; 8]) -> [u64; 8] {
+    let mut x = [0; 8];
+    for (x, a) in x.iter_mut().zip(a) {
+        let sum: u128 = a
+            .iter()
+            .zip(&v)
+            .map(|(&a, &v)| u128::from(a) * u128::from(v))
+            .sum();
+        *x = (sum % u128::from(P)) as u64;
+    }
+    x
+}
+
+/// `x mod P` up to one extra `P`, for `x` below 2^62.
+#[inline(always)]
+fn fold(x: u64x8) -> u64x8 {
+    (x & P) + (x >> 31)
+}
+
+/// MRG8, an order-8 multiple recursive generator modulo 2^31 - 1.
+///
+/// - Source: Nakamura et al., "MRG8: Random Number Generation for the
+///   Exascale Era" (2018)
+/// - State: 248-bits
+/// - Output: 32-bits per lane, the high 16 bits of two consecutive values of
+///   one stream
+/// - Cycle Length: (2<sup>31</sup> - 1)<sup>8</sup> - 1 ≈ 2<sup>248</sup>
+/// - BigCrush: passed
+///
+/// Linear modulo a prime, so jumping any distance is a matrix power.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mrg8 {
+    /// The last 8 values, oldest first.
+    state: u64x8,
+}
+
+impl_debug! { Mrg8 { state } }
+
+impl_serde! {
+    Mrg8 { state } where |rng| {
+        if rng.state.ge(u64x8::splat(P)).any() || rng.state.eq(u64x8::splat(0)).all() {
+            Err("a value past the modulus, or an all-zero state")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl SimdRng for Mrg8 {
+    type Result = u32x8;
+
+    #[inline(always)]
+    fn generate(&mut self) -> u32x8 {
+        // the values are below 2^31, so a word takes the high 16 bits of two
+        let hi = self.step() >> 15;
+        let lo = self.step() >> 15;
+        (hi << 16 | lo).cast()
+    }
+}
+
+impl_rngcore! { Mrg8 }
+
+impl Mrg8 {
+    /// The next 8 values of the recurrence, each below 2^31 - 1.
+    #[inline(always)]
+    pub fn step(&mut self) -> u64x8 {
+        let mut sum = u64x8::splat(0);
+        for (j, column) in BLOCK.iter().enumerate() {
+            // fold each product so the 8 of them can't overflow
+            sum += fold(u64x8::splat(self.state.extract(j)) * *column);
+        }
+
+        let x = fold(sum);
+        self.state = x.ge(u64x8::splat(P)).select(x - P, x);
+        self.state
+    }
+
+    /// Advances by `steps` values, in O(log steps) 8x8 matrix products. Each
+    /// `generate` takes 16 values.
+    pub fn jump(&mut self, mut steps: u128) {
+        let mut state = self.lanes();
+        let mut power = companion();
+        while steps != 0 {
+            if steps & 1 != 0 {
+                state = mat_vec(&power, state);
+            }
+            steps >>= 1;
+            if steps != 0 {
+                power = mat_mul(&power, &power);
+            }
+        }
+        self.state = u64x8::from_slice_unaligned(&state);
+    }
+
+    /// Advances by 2^200 values.
+    ///
+    /// Cloning and long jumping hands out substreams that don't overlap the
+    /// original for 2^200 values, 2^48 of them fit in a period.
+    pub fn long_jump(&mut self) {
+        let mut power = companion();
+        for _ in 0..200 {
+            power = mat_mul(&power, &power);
+        }
+        let state = mat_vec(&power, self.lanes());
+        self.state = u64x8::from_slice_unaligned(&state);
+    }
+
+    fn lanes(&self) -> [u64; 8] {
+        let mut x = [0; 8];
+        self.state.write_to_slice_unaligned(&mut x);
+        x
+    }
+}
+
+impl SplittableRng for Mrg8 {
+    fn split(&mut self) -> Self {
+        reseed(self)
+    }
+}
+
+impl SeedableRng for Mrg8 {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut state = [0_u32; 8];
+        state.as_byte_slice_mut().copy_from_slice(&seed);
+
+        let mut x = [0_u64; 8];
+        for (x, &s) in x.iter_mut().zip(&state) {
+            *x = u64::from(u32::from_le(s)) % P;
+        }
+
+        // an all-zero state would only ever output zero
+        if x.iter().all(|&x| x == 0) {
+            x[0] = 1;
+        }
+
+        Self {
+            state: u64x8::from_slice_unaligned(&x),
+        }
+    }
+
+    impl_seed_from_u64! {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_is_eighth_power() {
+        let mut power = companion();
+        for _ in 0..2 {
+            power = mat_mul(&power, &power);
+        }
+        let power = mat_mul(&power, &power);
+
+        for (j, column) in BLOCK.iter().enumerate() {
+            for (i, row) in power.iter().enumerate() {
+                assert_eq!(column.extract(i), row[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_recurrence() {
+        let mut rng = Mrg8 {
+            state: u64x8::new(1, 2, 3, 4, 5, 6, 7, 8),
+        };
+        let mut jumped = rng.clone();
+
+        #[rustfmt::skip]
+        let expected = [
+            u64x8::new(1814932210, 702449812, 387633445, 690869568, 812082384, 870457223, 336023788, 746324461),
+            u64x8::new(1774013774, 458025046, 1223493898, 1594008669, 425514369, 636540052, 1064453080, 1390312987),
+        ];
+        for &e in &expected {
+            assert_eq!(rng.step(), e);
+        }
+
+        jumped.jump(16);
+        assert_eq!(jumped, rng);
+    }
+
+    #[test]
+    fn outputs_use_every_bit() {
+        let mut rng = Mrg8::seed_from_u64(0);
+        let mut or = 0;
+        for _ in 0..16 {
+            or |= rng.next_u64();
+        }
+        // bits 31 and 63 included
+        assert_eq!(or, u64::max_value());
+    }
+}
@@ -1,20 +1,73 @@
-// These are implemented as iterating between parameters. Using all 8
-// parameter sets at once requires `pmuludq` instructions for the appropriate
-// vector widths. This implementation only needs SSE2. Larger would need AVX2
-// or AVX512.
-
-use std::arch::x86_64::*;
+// `Mwc2`, `Mwc4` and `Mwc8` iterate between parameter sets in `u64x2`
+// buffers, which only needs SSE2's `pmuludq`. `MwcX4` and `MwcX8` use 4 or
+// all 8 parameter sets at once, one `vpmuludq` with AVX2 or AVX512. Other
+// targets use a portable multiply, see `widening.rs`.
 
+use super::widening;
 use rng_impl::*;
 
+/// Factors for multiply-with-carry, one per 64-bit lane.
+///
+/// Each `a * 2^32 - 1` is a safe prime, so a state with its carry below `a`
+/// is on a cycle of `a * 2^31 - 1` steps.
+const FACTORS: [u64; 8] = [
+    4294963023, 3947008974, 4162943475, 2654432763, 3874257210, 2936881968, 4294957665,
+    2811536238,
+];
+
+/// Reads `seed` as little-endian lanes of carry and value, each made a valid
+/// state for its factor.
+///
+/// A state `c * 2^32 + x` is valid below `a * 2^32 - 1`, which keeps the
+/// carry below `a`. The other fixed point is zero, which is moved to one.
+fn seed_states(seed: &[u8]) -> [u64; 8] {
+    let mut states = [0; 8];
+    for ((s, bytes), &a) in states.iter_mut().zip(seed.chunks(8)).zip(&FACTORS) {
+        let mut x = [0; 8];
+        x.copy_from_slice(bytes);
+        *s = match u64::from_le_bytes(x) % ((a << 32) - 1) {
+            0 => 1,
+            s => s,
+        };
+    }
+    states
+}
+
+/// Rejects states `seed_states` can't produce, lanes in `FACTORS` order.
+#[cfg(feature = "serde1")]
+fn check_states(states: &[u64]) -> Result<(), &'static str> {
+    for (&s, &a) in states.iter().zip(&FACTORS) {
+        if s == 0 || s >= (a << 32) - 1 {
+            return Err("a carry at or above its factor, or a fixed point");
+        }
+    }
+    Ok(())
+}
+
 /// Probability of correlation: 8^2 * l / 5e18 ≈ l * 1.28e-17
 /// Using the smallest cycle-length of the different parameters
 /// (Not considering the effect of different parameters)
+#[derive(Clone, PartialEq, Eq)]
 pub struct Mwc8 {
     buffer: [u64x2; 4],
     idx: u8,
 }
 
+impl_debug! { Mwc8 { buffer, idx } }
+
+impl_serde! {
+    Mwc8 { buffer, idx } where |rng| {
+        if rng.idx >= 4 {
+            return Err("a buffer index past the buffers");
+        }
+        let mut states = [0; 8];
+        for (s, b) in states.chunks_mut(2).zip(&rng.buffer) {
+            b.write_to_slice_unaligned(s);
+        }
+        check_states(&states)
+    }
+}
+
 impl_rngcore! { Mwc8 }
 
 impl SimdRng for Mwc8 {
@@ -22,21 +75,11 @@ impl SimdRng for Mwc8 {
 
     #[inline(always)]
     fn generate(&mut self) -> u64x2 {
-        // Factors for multiply-with-carry
-        const FACTORS: [u64x2; 4] = [
-            u64x2::new(4294963023, 3947008974),
-            u64x2::new(4162943475, 2654432763),
-            u64x2::new(3874257210, 2936881968),
-            u64x2::new(4294957665, 2811536238),
-        ];
-
         let x = self.buffer[self.idx as usize];
-        let f = FACTORS[self.idx as usize];
+        let f = u64x2::from_slice_unaligned(&FACTORS[2 * self.idx as usize..]);
 
         // widening multiply the low 32 bits of each lane
-        let mut y = u64x2::from_bits(unsafe {
-            _mm_mul_epu32(__m128i::from_bits(x), __m128i::from_bits(f))
-        });
+        let mut y = widening::mul_low32(x, f);
 
         y += x >> 32; // add old carry
         self.buffer[self.idx as usize] = y; // new x and carry
@@ -53,33 +96,40 @@ impl SimdRng for Mwc8 {
 }
 
 impl SeedableRng for Mwc8 {
-    type Seed = [u8; 0];
+    type Seed = Seed512;
 
-    fn from_seed(_seed: Self::Seed) -> Self {
-        unimplemented!("`SeedableRngs` is unimplemented for MWC PRNGs")
-    }
+    fn from_seed(seed: Self::Seed) -> Self {
+        let states = seed_states(seed.as_ref());
 
-    fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-        let mut seed = [u64x2::default(); 4];
-        rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+        let mut buffer = [u64x2::splat(0); 4];
+        for (b, s) in buffer.iter_mut().zip(states.chunks(2)) {
+            *b = u64x2::from_slice_unaligned(s);
+        }
 
-        // seeding is very complicated. I'm hoping improper seeding won't
-        // affect speed
-
-        Ok(Self {
-            buffer: seed,
-            idx: 0,
-        })
+        Self { buffer, idx: 0 }
     }
+
+    impl_seed_from_u64! {}
 }
 
 /// Probability of correlation: 2^2 * l / 5e18 ≈ l * 8e-19
 /// Using the smallest cycle-length of the different parameters
 /// (Not considering the effect of different parameters)
+#[derive(Clone, PartialEq, Eq)]
 pub struct Mwc2 {
     state: u64x2,
 }
 
+impl_debug! { Mwc2 { state } }
+
+impl_serde! {
+    Mwc2 { state } where |rng| {
+        let mut states = [0; 2];
+        rng.state.write_to_slice_unaligned(&mut states);
+        check_states(&states)
+    }
+}
+
 impl_rngcore! { Mwc2 }
 
 impl SimdRng for Mwc2 {
@@ -87,13 +137,10 @@ impl SimdRng for Mwc2 {
 
     #[inline(always)]
     fn generate(&mut self) -> u64x2 {
-        // Factors for multiply-with-carry
-        const FACTORS: u64x2 = u64x2::new(4294963023, 3947008974);
+        let f = u64x2::from_slice_unaligned(&FACTORS);
 
         // widening multiply the low 32 bits of each lane
-        let mut y = u64x2::from_bits(unsafe {
-            _mm_mul_epu32(__m128i::from_bits(self.state), __m128i::from_bits(FACTORS))
-        });
+        let mut y = widening::mul_low32(self.state, f);
 
         y += self.state >> 32; // add old carry
         self.state = y; // new x and carry
@@ -109,31 +156,38 @@ impl SimdRng for Mwc2 {
 }
 
 impl SeedableRng for Mwc2 {
-    type Seed = [u8; 0];
+    type Seed = [u8; 16];
 
-    fn from_seed(_seed: Self::Seed) -> Self {
-        unimplemented!("`SeedableRngs` is unimplemented for MWC PRNGs")
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self {
+            state: u64x2::from_slice_unaligned(&seed_states(&seed)),
+        }
     }
 
-    fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-        let mut seed = [u64x2::default(); 1];
-        rng.try_fill_bytes(seed.as_byte_slice_mut())?;
-
-        // seeding is very complicated. I'm hoping improper seeding won't
-        // affect speed
-
-        Ok(Self { state: seed[0] })
-    }
+    impl_seed_from_u64! {}
 }
 
 /// Probability of correlation: 4^2 * l / 5e18 ≈ l * 3.2e-18
 /// Using the smallest cycle-length of the different parameters
 /// (Not considering the effect of different parameters)
+#[derive(Clone, PartialEq, Eq)]
 pub struct Mwc4 {
     buffer: [u64x2; 2],
     idx: bool,
 }
 
+impl_debug! { Mwc4 { buffer, idx } }
+
+impl_serde! {
+    Mwc4 { buffer, idx } where |rng| {
+        let mut states = [0; 4];
+        for (s, b) in states.chunks_mut(2).zip(&rng.buffer) {
+            b.write_to_slice_unaligned(s);
+        }
+        check_states(&states)
+    }
+}
+
 impl_rngcore! { Mwc4 }
 
 impl SimdRng for Mwc4 {
@@ -141,19 +195,11 @@ impl SimdRng for Mwc4 {
 
     #[inline(always)]
     fn generate(&mut self) -> u64x2 {
-        // Factors for multiply-with-carry
-        const FACTORS: [u64x2; 2] = [
-            u64x2::new(4294963023, 3947008974),
-            u64x2::new(4162943475, 2654432763),
-        ];
-
         let x = self.buffer[self.idx as usize];
-        let f = FACTORS[self.idx as usize];
+        let f = u64x2::from_slice_unaligned(&FACTORS[2 * self.idx as usize..]);
 
         // widening multiply the low 32 bits of each lane
-        let mut y = u64x2::from_bits(unsafe {
-            _mm_mul_epu32(__m128i::from_bits(x), __m128i::from_bits(f))
-        });
+        let mut y = widening::mul_low32(x, f);
 
         y += x >> 32; // add old carry
         self.buffer[self.idx as usize] = y; // new x and carry
@@ -170,22 +216,120 @@ impl SimdRng for Mwc4 {
 }
 
 impl SeedableRng for Mwc4 {
-    type Seed = [u8; 0];
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let states = seed_states(&seed);
 
-    fn from_seed(_seed: Self::Seed) -> Self {
-        unimplemented!("`SeedableRngs` is unimplemented for MWC PRNGs")
+        Self {
+            buffer: [
+                u64x2::from_slice_unaligned(&states[..2]),
+                u64x2::from_slice_unaligned(&states[2..4]),
+            ],
+            idx: false,
+        }
     }
 
-    fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-        let mut seed = [u64x2::default(); 2];
-        rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+    impl_seed_from_u64! {}
+}
 
-        // seeding is very complicated. I'm hoping improper seeding won't
-        // affect speed
+macro_rules! make_mwc_x {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        /// Multiply-with-carry with a different factor in each lane, all
+        /// stepped at once.
+        ///
+        /// - State: 64-bits per lane
+        /// - Output: 64-bits per lane
+        /// - Cycle Length: `a * 2^31 - 1` per lane, at least ≈ 2<sup>62</sup>
+        ///
+        /// From the same seed, lane pairs are the buffers `Mwc4`/`Mwc8` step
+        /// in turn, so the outputs are theirs in fewer calls.
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $rng_name {
+            state: $vector,
+        }
+
+        impl_debug! { $rng_name { state } }
+
+        impl_serde! {
+            $rng_name { state } where |rng| {
+                let mut states = [0; 8];
+                rng.state.write_to_slice_unaligned(&mut states[..$vector::lanes()]);
+                check_states(&states[..$vector::lanes()])
+            }
+        }
+
+        impl_rngcore! { $rng_name }
+
+        impl SimdRng for $rng_name {
+            type Result = $vector;
+
+            #[inline(always)]
+            fn generate(&mut self) -> $vector {
+                let x = self.state;
+                let f = $vector::from_slice_unaligned(&FACTORS);
+
+                // widening multiply the low 32 bits of each lane, the factors
+                // are below 2^32 already
+                let mut y = (x & 0xffff_ffff) * f;
+
+                y += x >> 32; // add old carry
+                self.state = y; // new x and carry
+
+                y ^= y << 30; // output function
+                y ^= y >> 35;
+                y ^= y << 13;
+
+                y
+            }
+        }
+
+        impl SeedableRng for $rng_name {
+            type Seed = $seed;
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                Self {
+                    state: $vector::from_slice_unaligned(&seed_states(seed.as_ref())),
+                }
+            }
+
+            impl_seed_from_u64! {}
+        }
+    };
+}
 
-        Ok(Self {
-            buffer: seed,
-            idx: false,
-        })
+make_mwc_x! { MwcX4, u64x4, [u8; 32] }
+make_mwc_x! { MwcX8, u64x8, Seed512 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carry_below_factor() {
+        let rng = MwcX8::from_seed(Seed512([0xff; 64]));
+        for (i, &a) in FACTORS.iter().enumerate() {
+            assert!(rng.state.extract(i) >> 32 < a);
+        }
+
+        // zero is a fixed point
+        let mut rng = MwcX8::from_seed(Seed512::default());
+        assert_ne!(rng.generate(), u64x8::splat(0));
+    }
+
+    #[test]
+    fn wide_matches_round_robin() {
+        let mut seed = Seed512::default();
+        SplitMix64::new(0).fill_bytes(seed.as_mut());
+        let mut wide = MwcX8::from_seed(seed);
+        let mut round_robin = Mwc8::from_seed(seed);
+
+        for _ in 0..4 {
+            let x = wide.generate();
+            for i in 0..4 {
+                let y = round_robin.generate();
+                assert_eq!([x.extract(2 * i), x.extract(2 * i + 1)], [y.extract(0), y.extract(1)]);
+            }
+        }
     }
 }
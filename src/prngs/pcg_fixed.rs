@@ -6,12 +6,19 @@
 use rng_impl::*;
 
 macro_rules! make_pcg_xsh {
-    ($rng_name:ident, $vector:ident, $vec32:ident) => {
+    ($rng_name:ident, $vector:ident, $vec32:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             state: $vector,
             inc: $vector,
         }
 
+        impl_debug! { $rng_name { state, inc } }
+
+        impl_serde! { $rng_name { state, inc } }
+
+        impl_lcg_advance! { $rng_name, $vector, 6364136223846793005 }
+
         impl_rngcore! { $rng_name }
 
         impl SimdRng for $rng_name {
@@ -36,15 +43,10 @@ macro_rules! make_pcg_xsh {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
-
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 2];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 2);
 
                 let mut pcg = Self {
                     state: seed[0],
@@ -53,8 +55,10 @@ macro_rules! make_pcg_xsh {
 
                 pcg.state = pcg.state * 6364136223846793005 + pcg.inc;
 
-                Ok(pcg)
+                pcg
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -63,18 +67,25 @@ macro_rules! make_pcg_xsh {
 // (multiple parameters could be used)
 // (stream selection is possible)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                          Probability
-make_pcg_xsh! { PcgFixedXsh32x2, u64x2, u32x2 } // ≈ 2^2 * l / 2^32 ≈ l * 2^-30
-make_pcg_xsh! { PcgFixedXsh32x4, u64x4, u32x4 } // ≈ 4^2 * l / 2^32 ≈ l * 2^-28
-make_pcg_xsh! { PcgFixedXsh32x8, u64x8, u32x8 } // ≈ 8^2 * l / 2^32 ≈ l * 2^-26
+// Listing probability of overlap somewhere:                                    Probability
+make_pcg_xsh! { PcgFixedXsh32x2, u64x2, u32x2, [u8; 32] } // ≈ 2^2 * l / 2^32 ≈ l * 2^-30
+make_pcg_xsh! { PcgFixedXsh32x4, u64x4, u32x4, Seed512  } // ≈ 4^2 * l / 2^32 ≈ l * 2^-28
+make_pcg_xsh! { PcgFixedXsh32x8, u64x8, u32x8, Seed1024 } // ≈ 8^2 * l / 2^32 ≈ l * 2^-26
 
 macro_rules! make_pcg_xsl {
-    ($rng_name:ident, $vector:ident, $vec32:ident) => {
+    ($rng_name:ident, $vector:ident, $vec32:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             state: $vector,
             inc: $vector,
         }
 
+        impl_debug! { $rng_name { state, inc } }
+
+        impl_serde! { $rng_name { state, inc } }
+
+        impl_lcg_advance! { $rng_name, $vector, 6364136223846793005 }
+
         impl_rngcore! { $rng_name }
 
         impl SimdRng for $rng_name {
@@ -99,15 +110,10 @@ macro_rules! make_pcg_xsl {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
-
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 2];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 2);
 
                 let mut pcg = Self {
                     state: seed[0],
@@ -116,8 +122,10 @@ macro_rules! make_pcg_xsl {
 
                 pcg.state = pcg.state * 6364136223846793005 + pcg.inc;
 
-                Ok(pcg)
+                pcg
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -126,7 +134,7 @@ macro_rules! make_pcg_xsl {
 // (multiple parameters could be used)
 // (stream selection is possible)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                          Probability
-make_pcg_xsl! { PcgFixedXsl32x2, u64x2, u32x2 } // ≈ 2^2 * l / 2^64 ≈ l * 2^-62
-make_pcg_xsl! { PcgFixedXsl32x4, u64x4, u32x4 } // ≈ 4^2 * l / 2^64 ≈ l * 2^-60
-make_pcg_xsl! { PcgFixedXsl32x8, u64x8, u32x8 } // ≈ 8^2 * l / 2^64 ≈ l * 2^-58
+// Listing probability of overlap somewhere:                                    Probability
+make_pcg_xsl! { PcgFixedXsl32x2, u64x2, u32x2, [u8; 32] } // ≈ 2^2 * l / 2^64 ≈ l * 2^-62
+make_pcg_xsl! { PcgFixedXsl32x4, u64x4, u32x4, Seed512  } // ≈ 4^2 * l / 2^64 ≈ l * 2^-60
+make_pcg_xsl! { PcgFixedXsl32x8, u64x8, u32x8, Seed1024 } // ≈ 8^2 * l / 2^64 ≈ l * 2^-58
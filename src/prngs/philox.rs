@@ -0,0 +1,338 @@
+// Each 128-bit group of lanes is its own Random123 stream, with its own key
+// and counter, so `Philox4x32x16` runs four streams side by side.
+
+use rng_impl::*;
+
+macro_rules! impl_philox_counter {
+    ($rng_name:ident, $wide:ident, $swap:tt) => {
+        impl $rng_name {
+            /// `[a, b, a, b, ...]`
+            #[inline(always)]
+            fn alternate(a: u64, b: u64) -> $wide {
+                let mut v = $wide::splat(a);
+                for i in (1..$wide::lanes()).step_by(2) {
+                    v = v.replace(i, b);
+                }
+                v
+            }
+
+            /// Adds one to each stream's 128-bit counter.
+            #[inline(always)]
+            fn increment(ctr: $wide) -> $wide {
+                let one = Self::alternate(1, 0);
+                let ctr = ctr + one;
+                // carry from the low 64 bits of each stream to the high
+                let carry = ctr.eq($wide::splat(0)).select(one, $wide::splat(0));
+                ctr + shuffle!(carry, $swap)
+            }
+        }
+    };
+}
+
+macro_rules! make_philox4x32 {
+    (
+        $rng_name:ident, $vector:ident, $wide:ident, $seed:ty, $rounds:expr, $reverse:tt,
+        $swap:tt
+    ) => {
+        /// Philox4x32 from [Random123], one stream per 128 bits.
+        ///
+        /// - Source: Salmon et al., "Parallel Random Numbers: As Easy as 1, 2,
+        ///   3" (2011)
+        /// - Output: 128-bits per stream
+        /// - Cycle Length: 2<sup>128</sup> per stream
+        /// - BigCrush: passed
+        ///
+        /// `Philox4x32x*` run Random123's default 10 rounds, `Philox4x32R7x*`
+        /// the 7 that are the fewest found Crush-resistant. Other round counts
+        /// are available through [`philox4x32_r`](fn.philox4x32_r.html).
+        ///
+        /// [Random123]: http://www.deshawresearch.com/resources_random123.html
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $rng_name {
+            ctr: $vector,
+            key: $vector,
+        }
+
+        impl_debug! { $rng_name { ctr, key } }
+
+        impl_serde! { $rng_name { ctr, key } }
+
+        impl_philox_counter! { $rng_name, $wide, $swap }
+
+        impl $rng_name {
+            /// `key` holds each stream's two key words in lanes 0 and 2.
+            #[inline(always)]
+            fn block(mut ctr: $vector, mut key: $vector, rounds: usize) -> $vector {
+                let mul = Self::alternate(0xD2511F53, 0xCD9E8D57);
+                let bump = $vector::from_bits(Self::alternate(0x9E3779B9, 0xBB67AE85));
+
+                for round in 0..rounds {
+                    if round > 0 {
+                        key += bump;
+                    }
+
+                    // widening multiply of lanes 0 and 2, LLVM lowers this
+                    // to `pmuludq`, which `widening::mul_low32` calls directly
+                    let c = $wide::from_bits(ctr);
+                    let product = $vector::from_bits((c & 0xffff_ffff) * mul);
+
+                    // [hi1 ^ c1 ^ k0, lo1, hi0 ^ c3 ^ k1, lo0]
+                    ctr = shuffle!(product, $reverse) ^ $vector::from_bits(c >> 32) ^ key;
+                }
+
+                ctr
+            }
+        }
+
+        impl SimdRng for $rng_name {
+            type Result = $vector;
+
+            #[inline(always)]
+            fn generate(&mut self) -> $vector {
+                let result = Self::block(self.ctr, self.key, $rounds);
+                self.ctr = $vector::from_bits(Self::increment($wide::from_bits(self.ctr)));
+                result
+            }
+        }
+
+        impl_rngcore! { $rng_name }
+
+        impl SeedableRng for $rng_name {
+            type Seed = $seed;
+
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let s = read_seed!(seed, $vector; 2);
+
+                Self {
+                    ctr: s[0],
+                    // only lanes 0 and 2 of each stream are key words
+                    key: s[1] & $vector::from_bits($wide::splat(0xffff_ffff)),
+                }
+            }
+
+            impl_seed_from_u64! {}
+        }
+    };
+}
+
+macro_rules! make_philox4x32_widths {
+    ($rng_x4:ident, $rng_x8:ident, $rng_x16:ident, $rounds:expr) => {
+        #[rustfmt::skip]
+        make_philox4x32! { $rng_x4,  u32x4,  u64x2, [u8; 32], $rounds, [3, 2, 1, 0], [1, 0] }
+        #[rustfmt::skip]
+        make_philox4x32! { $rng_x8,  u32x8,  u64x4, Seed512,  $rounds, [3, 2, 1, 0, 7, 6, 5, 4], [1, 0, 3, 2] }
+        #[rustfmt::skip]
+        make_philox4x32! { $rng_x16, u32x16, u64x8, Seed1024, $rounds, [3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12], [1, 0, 3, 2, 5, 4, 7, 6] }
+    };
+}
+
+make_philox4x32_widths! { Philox4x32x4, Philox4x32x8, Philox4x32x16, 10 }
+make_philox4x32_widths! { Philox4x32R7x4, Philox4x32R7x8, Philox4x32R7x16, 7 }
+
+/// The Philox4x32 bijection with `rounds` rounds, as Random123's
+/// `philox4x32_R`.
+pub fn philox4x32_r(rounds: usize, ctr: u32x4, key: u32x2) -> u32x4 {
+    let key = u32x4::new(key.extract(0), 0, key.extract(1), 0);
+    Philox4x32x4::block(ctr, key, rounds)
+}
+
+macro_rules! make_philox2x64 {
+    ($rng_name:ident, $vector:ident, $seed:ty, $rounds:expr, $swap:tt, $interleave:tt) => {
+        /// Philox2x64-10 from [Random123], one stream per 128 bits.
+        ///
+        /// - Source: Salmon et al., "Parallel Random Numbers: As Easy as 1, 2,
+        ///   3" (2011)
+        /// - Output: 128-bits per stream
+        /// - Cycle Length: 2<sup>128</sup> per stream
+        /// - BigCrush: passed
+        ///
+        /// There is no SIMD 64-bit widening multiply, it is built from four
+        /// 32-bit ones, so this is slower than
+        /// [`Philox4x32x4`](struct.Philox4x32x4.html). Other round counts are
+        /// available through [`philox2x64_r`](fn.philox2x64_r.html).
+        ///
+        /// [Random123]: http://www.deshawresearch.com/resources_random123.html
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $rng_name {
+            ctr: $vector,
+            key: $vector,
+        }
+
+        impl_debug! { $rng_name { ctr, key } }
+
+        impl_serde! { $rng_name { ctr, key } }
+
+        impl_philox_counter! { $rng_name, $vector, $swap }
+
+        impl $rng_name {
+            /// The high 64 bits of `a * b`, lanewise.
+            #[inline(always)]
+            fn mulhi(a: $vector, b: u64) -> $vector {
+                const LOW: u64 = 0xffff_ffff;
+                let (a_lo, a_hi) = (a & LOW, a >> 32);
+                let (b_lo, b_hi) = (b & LOW, b >> 32);
+
+                let lo_lo = a_lo * b_lo;
+                let lo_hi = a_lo * b_hi;
+                let hi_lo = a_hi * b_lo;
+                let hi_hi = a_hi * b_hi;
+
+                let mid = (lo_lo >> 32) + (lo_hi & LOW) + (hi_lo & LOW);
+                hi_hi + (lo_hi >> 32) + (hi_lo >> 32) + (mid >> 32)
+            }
+
+            /// `key` holds each stream's key word in lane 0.
+            #[inline(always)]
+            fn block(mut ctr: $vector, mut key: $vector, rounds: usize) -> $vector {
+                const MUL: u64 = 0xD2B74407B1CE6E93;
+                let bump = Self::alternate(0x9E3779B97F4A7C15, 0);
+
+                for round in 0..rounds {
+                    if round > 0 {
+                        key += bump;
+                    }
+
+                    // only lane 0 of each stream is multiplied, the rest is
+                    // discarded by the interleave
+                    let hi = Self::mulhi(ctr, MUL);
+                    let lo = ctr * MUL;
+
+                    // [hi0 ^ c1 ^ k, lo0]
+                    let x = hi ^ shuffle!(ctr, $swap) ^ key;
+                    ctr = shuffle!(x, lo, $interleave);
+                }
+
+                ctr
+            }
+        }
+
+        impl SimdRng for $rng_name {
+            type Result = $vector;
+
+            #[inline(always)]
+            fn generate(&mut self) -> $vector {
+                let result = Self::block(self.ctr, self.key, $rounds);
+                self.ctr = Self::increment(self.ctr);
+                result
+            }
+        }
+
+        impl_rngcore! { $rng_name }
+
+        impl SeedableRng for $rng_name {
+            type Seed = $seed;
+
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let s = read_seed!(seed, $vector; 2);
+
+                Self {
+                    ctr: s[0],
+                    // only lane 0 of each stream is a key word
+                    key: s[1] & Self::alternate(!0, 0),
+                }
+            }
+
+            impl_seed_from_u64! {}
+        }
+    };
+}
+
+#[rustfmt::skip]
+make_philox2x64! { Philox2x64x2, u64x2, [u8; 32], 10, [1, 0], [0, 2] }
+#[rustfmt::skip]
+make_philox2x64! { Philox2x64x4, u64x4, Seed512,  10, [1, 0, 3, 2], [0, 4, 2, 6] }
+#[rustfmt::skip]
+make_philox2x64! { Philox2x64x8, u64x8, Seed1024, 10, [1, 0, 3, 2, 5, 4, 7, 6], [0, 8, 2, 10, 4, 12, 6, 14] }
+
+/// The Philox2x64 bijection with `rounds` rounds, as Random123's
+/// `philox2x64_R`.
+pub fn philox2x64_r(rounds: usize, ctr: u64x2, key: u64) -> u64x2 {
+    Philox2x64x2::block(ctr, u64x2::new(key, 0), rounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Random123's kat_vectors
+    #[test]
+    fn philox4x32_kat() {
+        let kat = |rounds, ctr: [u32; 4], key: [u32; 2], expected: [u32; 4]| {
+            let ctr = u32x4::new(ctr[0], ctr[1], ctr[2], ctr[3]);
+            let key = u32x2::new(key[0], key[1]);
+            let expected = u32x4::new(expected[0], expected[1], expected[2], expected[3]);
+            assert_eq!(philox4x32_r(rounds, ctr, key), expected);
+        };
+
+        let pi_ctr = [0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344];
+        let pi_key = [0xa4093822, 0x299f31d0];
+
+        kat(7, [0; 4], [0; 2], [0x5f6fb709, 0x0d893f64, 0x4f121f81, 0x4f730a48]);
+        kat(7, [!0; 4], [!0; 2], [0x5207ddc2, 0x45165e59, 0x4d8ee751, 0x8c52f662]);
+        kat(7, pi_ctr, pi_key, [0x4dfccaba, 0x190a87f0, 0xc47362ba, 0xb6b5242a]);
+        kat(10, [0; 4], [0; 2], [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]);
+        kat(10, [!0; 4], [!0; 2], [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]);
+        kat(10, pi_ctr, pi_key, [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]);
+    }
+
+    #[test]
+    fn philox2x64_kat() {
+        let kat = |rounds, ctr: [u64; 2], key, expected: [u64; 2]| {
+            let ctr = u64x2::new(ctr[0], ctr[1]);
+            let expected = u64x2::new(expected[0], expected[1]);
+            assert_eq!(philox2x64_r(rounds, ctr, key), expected);
+        };
+
+        let pi_ctr = [0x243f6a8885a308d3, 0x13198a2e03707344];
+        let pi_key = 0xa4093822299f31d0;
+
+        kat(7, [0; 2], 0, [0xb41da69fbfefc666, 0x511e9ce1a5534056]);
+        kat(7, [!0; 2], !0, [0xa4696cc04462015d, 0x724782dae17169e9]);
+        kat(7, pi_ctr, pi_key, [0x98ed1534392bf372, 0x67528b1568882fd5]);
+        kat(10, [0; 2], 0, [0xca00a0459843d731, 0x66c24222c9a845b5]);
+        kat(10, [!0; 2], !0, [0x65b021d60cd8310f, 0x4d02f3222f86df20]);
+        kat(10, pi_ctr, pi_key, [0x0a5e742c2997341c, 0xb0f883d38000de5d]);
+    }
+
+    #[test]
+    fn philox_streams() {
+        // the second stream's counter carries into its high word
+        let mut seed = Seed512::default();
+        for (i, byte) in seed.as_mut().iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        seed.as_mut()[16..24].copy_from_slice(&[0xff; 8]);
+
+        let mut rng = Philox2x64x4::from_seed(seed);
+        let ctr = rng.ctr;
+        let key = rng.key;
+        for i in 0..2 {
+            let x = rng.generate();
+            for stream in 0..2 {
+                let lane = 2 * stream;
+                let c = u128::from(ctr.extract(lane)) | u128::from(ctr.extract(lane + 1)) << 64;
+                let c = c + i;
+                let c = u64x2::new(c as u64, (c >> 64) as u64);
+                let block = philox2x64_r(10, c, key.extract(lane));
+                assert_eq!(x.extract(lane), block.extract(0));
+                assert_eq!(x.extract(lane + 1), block.extract(1));
+            }
+        }
+    }
+
+    #[test]
+    fn philox4x32_rounds() {
+        let mut rng = Philox4x32R7x8::seed_from_u64(0);
+        let (ctr, key) = (rng.ctr, rng.key);
+        let x = rng.generate();
+        for stream in 0..2 {
+            let lanes = |v: u32x8| {
+                let i = 4 * stream;
+                u32x4::new(v.extract(i), v.extract(i + 1), v.extract(i + 2), v.extract(i + 3))
+            };
+            let key = lanes(key);
+            let key = u32x2::new(key.extract(0), key.extract(2));
+            assert_eq!(lanes(x), philox4x32_r(7, lanes(ctr), key));
+        }
+    }
+}
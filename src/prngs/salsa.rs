@@ -0,0 +1,245 @@
+//! The Salsa20 family of stream ciphers, ChaCha's predecessor.
+//!
+//! https://cr.yp.to/snuffle.html
+
+use rand::CryptoRng;
+
+use rng_impl::*;
+
+/// "expand 32-byte k", on the diagonal of the state.
+const SIGMA: u32x4 = u32x4::new(0x61707865, 0x3320646E, 0x79622D32, 0x6B206574);
+
+/// The state in its usual word order: key words 1..=4 and 11..=14, the
+/// 64-bit nonce in 6 and 7, and the 64-bit block counter in 8 and 9.
+fn initial_state(key: [u8; 32], input: u32x4) -> u32x16 {
+    let key = u32x8::from_bits(u8x32::from_slice_unaligned(&key));
+
+    let mut words = [0_u32; 16];
+    for (j, &i) in [0, 5, 10, 15].iter().enumerate() {
+        words[i] = SIGMA.extract(j);
+    }
+    for (j, &i) in [1, 2, 3, 4, 11, 12, 13, 14].iter().enumerate() {
+        words[i] = key.extract(j);
+    }
+    for (j, &i) in [6, 7, 8, 9].iter().enumerate() {
+        words[i] = input.extract(j);
+    }
+    u32x16::from_slice_unaligned(&words)
+}
+
+/// The Salsa20 rounds, without the final addition.
+///
+/// Each vector holds one of the state's diagonals, so the column round works
+/// on whole vectors and the row round only needs lane rotations.
+#[inline(always)]
+fn core(x: u32x16, rounds: usize) -> u32x16 {
+    let mut a: u32x4 = shuffle!(x, [0, 5, 10, 15]);
+    let mut b: u32x4 = shuffle!(x, [4, 9, 14, 3]);
+    let mut c: u32x4 = shuffle!(x, [8, 13, 2, 7]);
+    let mut d: u32x4 = shuffle!(x, [12, 1, 6, 11]);
+
+    for _double_round in 0..rounds / 2 {
+        // columns
+        b ^= (a + d).rotate_left_opt(7);
+        c ^= (b + a).rotate_left_opt(9);
+        d ^= (c + b).rotate_left_opt(13);
+        a ^= (d + c).rotate_left_opt(18);
+
+        // rows
+        b = shuffle!(b, [3, 0, 1, 2]);
+        c = shuffle!(c, [2, 3, 0, 1]);
+        d = shuffle!(d, [1, 2, 3, 0]);
+        d ^= (a + b).rotate_left_opt(7);
+        c ^= (d + a).rotate_left_opt(9);
+        b ^= (c + d).rotate_left_opt(13);
+        a ^= (b + c).rotate_left_opt(18);
+        b = shuffle!(b, [1, 2, 3, 0]);
+        c = shuffle!(c, [2, 3, 0, 1]);
+        d = shuffle!(d, [3, 0, 1, 2]);
+    }
+
+    let ab: u32x8 = shuffle!(a, b, [0, 1, 2, 3, 4, 5, 6, 7]);
+    let cd: u32x8 = shuffle!(c, d, [0, 1, 2, 3, 4, 5, 6, 7]);
+    let abcd: u32x16 = shuffle!(
+        ab,
+        cd,
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
+    );
+    shuffle!(
+        abcd,
+        [0, 13, 10, 7, 4, 1, 14, 11, 8, 5, 2, 15, 12, 9, 6, 3]
+    )
+}
+
+/// HSalsa20, for deriving XSalsa20 subkeys: the Salsa20 rounds on `key` and
+/// a 128-bit `input` in place of nonce and counter, keeping the diagonal and
+/// the input words without the final addition.
+///
+/// - Source: Bernstein, "Extending the Salsa20 nonce" (2008)
+pub fn hsalsa20(key: [u8; 32], input: [u8; 16]) -> [u8; 32] {
+    let input = u32x4::from_bits(u8x16::from_slice_unaligned(&input));
+    let x = core(initial_state(key, input), 20);
+    let words: u32x8 = shuffle!(x, [0, 5, 10, 15, 6, 7, 8, 9]);
+
+    let mut subkey = [0; 32];
+    u8x32::from_bits(words).write_to_slice_unaligned(&mut subkey);
+    subkey
+}
+
+macro_rules! make_salsa {
+    ($rng_name:ident, $rounds:expr) => {
+        /// Salsa20, with the round count in its name.
+        ///
+        /// A single stream, one 64-byte block per call. Outputs are the
+        /// keystream in order.
+        ///
+        /// The 64-bit block counter and 64-bit nonce are the original Salsa20
+        /// layout, `stream` is the nonce. See `with_extended_nonce` for
+        /// XSalsa20's 192-bit nonces, which are safe to pick at random.
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $rng_name {
+            state: u32x16,
+        }
+
+        impl_debug! { $rng_name { state } }
+
+        impl_serde! { $rng_name { state } }
+
+        impl_rngcore! { $rng_name }
+
+        impl SimdRng for $rng_name {
+            type Result = u32x16;
+
+            #[inline(always)]
+            fn generate(&mut self) -> u32x16 {
+                let result = core(self.state, $rounds) + self.state;
+                let words = u64x8::from_bits(self.state) + u64x8::new(0, 0, 0, 0, 1, 0, 0, 0);
+                self.state = u32x16::from_bits(words);
+                result
+            }
+        }
+
+        impl $rng_name {
+            /// XSalsa: HSalsa20 derives a subkey from `key` and the first 16
+            /// bytes of `nonce`, the last 8 bytes are the stream.
+            ///
+            /// With 192 bits, nonces chosen at random won't collide, so every
+            /// stream can be given one without coordination.
+            pub fn with_extended_nonce(key: [u8; 32], nonce: [u8; 24]) -> Self {
+                let mut input = [0; 16];
+                input.copy_from_slice(&nonce[..16]);
+                let mut stream = [0; 8];
+                stream.copy_from_slice(&nonce[16..]);
+
+                let mut rng = Self::from_seed(hsalsa20(key, input));
+                rng.set_stream(u64::from_le_bytes(stream));
+                rng
+            }
+
+            /// The block counter of the next output.
+            pub fn counter(&self) -> u64 {
+                u64x8::from_bits(self.state).extract(4)
+            }
+
+            /// Restarts output at block `counter` of the current stream.
+            pub fn set_counter(&mut self, counter: u64) {
+                self.state = u32x16::from_bits(u64x8::from_bits(self.state).replace(4, counter));
+            }
+
+            /// The stream of the next output.
+            pub fn stream(&self) -> u64 {
+                u64x8::from_bits(self.state).extract(3)
+            }
+
+            /// Switches to `stream`, keeping the block counter.
+            pub fn set_stream(&mut self, stream: u64) {
+                self.state = u32x16::from_bits(u64x8::from_bits(self.state).replace(3, stream));
+            }
+        }
+
+        impl SeedableRng for $rng_name {
+            type Seed = [u8; 32];
+
+            fn from_seed(seed: Self::Seed) -> Self {
+                Self {
+                    state: initial_state(seed, u32x4::splat(0)),
+                }
+            }
+
+            impl_seed_from_u64! {}
+        }
+    };
+}
+
+make_salsa! { Salsa8, 8 }
+make_salsa! { Salsa12, 12 }
+make_salsa! { Salsa20, 20 }
+
+impl CryptoRng for Salsa20 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "Salsa20 specification", section 10
+    #[test]
+    fn salsa20_spec_expansion() {
+        let mut key = [0; 32];
+        for (i, k) in key.iter_mut().enumerate() {
+            *k = i as u8 + if i < 16 { 1 } else { 185 };
+        }
+        let mut rng = Salsa20::from_seed(key);
+        // n = (101, 102, ..., 116)
+        rng.set_stream(0x6c6b_6a69_6867_6665);
+        rng.set_counter(0x7473_7271_706f_6e6d);
+
+        let mut bytes = [0; 64];
+        rng.fill_bytes(&mut bytes);
+
+        let expected = [
+            69, 37, 68, 39, 41, 15, 107, 193, 255, 139, 122, 6, 170, 233, 217, 98, 89, 144, 182,
+            106, 21, 51, 200, 65, 239, 49, 222, 34, 215, 114, 40, 126, 104, 197, 7, 225, 197, 153,
+            31, 2, 102, 78, 76, 176, 84, 245, 246, 184, 177, 160, 133, 130, 6, 72, 149, 119, 192,
+            195, 132, 236, 234, 103, 246, 74,
+        ];
+        assert_eq!(&bytes[..], &expected[..]);
+        assert_eq!(rng.counter(), 0x7473_7271_706f_6e6e);
+    }
+
+    // the first block of the XSalsa20 test in NaCl, `tests/stream3.c`
+    #[test]
+    fn xsalsa20_keystream() {
+        let key = [
+            0x1b, 0x27, 0x55, 0x64, 0x73, 0xe9, 0x85, 0xd4, 0x62, 0xcd, 0x51, 0x19, 0x7a, 0x9a,
+            0x46, 0xc7, 0x60, 0x09, 0x54, 0x9e, 0xac, 0x64, 0x74, 0xf2, 0x06, 0xc4, 0xee, 0x08,
+            0x44, 0xf6, 0x83, 0x89,
+        ];
+        let nonce = [
+            0x69, 0x69, 0x6e, 0xe9, 0x55, 0xb6, 0x2b, 0x73, 0xcd, 0x62, 0xbd, 0xa8, 0x75, 0xfc,
+            0x73, 0xd6, 0x82, 0x19, 0xe0, 0x03, 0x6b, 0x7a, 0x0b, 0x37,
+        ];
+        let mut rng = Salsa20::with_extended_nonce(key, nonce);
+
+        #[rustfmt::skip]
+        let expected = u32x16::new(
+            0x25a7a6ee, 0x91721e1c, 0xcbc2116d, 0x253c4d21,
+            0x1d123925, 0x654e238e, 0xa41f652d, 0x80f8cfc8,
+            0x5a649e30, 0xa6e0e974, 0xac43820d, 0xb57a17d9,
+            0x8deb1b1a, 0x705d2f5a, 0x5e3c090c, 0x96578555,
+        );
+        assert_eq!(rng.generate(), expected);
+    }
+
+    #[test]
+    fn reduced_rounds() {
+        let first = |x: u32x16| -> u32x4 { shuffle!(x, [0, 1, 2, 3]) };
+        assert_eq!(
+            first(Salsa8::from_seed([0; 32]).generate()),
+            u32x4::new(0xa51d599f, 0x54239cf9, 0x8691ea45, 0x1b68ad6e)
+        );
+        assert_eq!(
+            first(Salsa12::from_seed([0; 32]).generate()),
+            u32x4::new(0xf8a278bd, 0x3c568a11, 0xf2b41d76, 0xda55e0fb)
+        );
+    }
+}
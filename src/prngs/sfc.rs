@@ -3,13 +3,14 @@
 use rng_impl::*;
 
 macro_rules! make_sfc_simd {
-    ($rng_name:ident, $vector:ident, $rot:expr, $shr:expr, $shl:expr) => {
+    ($rng_name:ident, $vector:ident, $seed:ty, $rot:expr, $shr:expr, $shl:expr) => {
         /// An SIMD implementation of Chris Doty-Humphrey's Small Fast Counting RNG
         ///
         /// - Author: Chris Doty-Humphrey
         /// - License: Public domain
         /// - Source: [PractRand](http://pracrand.sourceforge.net/)
         /// - Passes BigCrush and PractRand
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             a: $vector,
             b: $vector,
@@ -17,6 +18,10 @@ macro_rules! make_sfc_simd {
             counter: $vector,
         }
 
+        impl_debug! { $rng_name { a, b, c, counter } }
+
+        impl_serde! { $rng_name { a, b, c, counter } }
+
         impl_rngcore! { $rng_name }
 
         impl SimdRng for $rng_name {
@@ -34,34 +39,31 @@ macro_rules! make_sfc_simd {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 3);
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 3];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
-
-                Ok(Self {
+                Self {
                     a: seed[0],
                     b: seed[1],
                     c: seed[2],
                     counter: $vector::splat(1),
-                })
+                }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 
-    ( 64bit: $rng_name:ident, $vector:ident ) => {
-        make_sfc_simd! { $rng_name, $vector, 24, 11, 3 }
+    ( 64bit: $rng_name:ident, $vector:ident, $seed:ty ) => {
+        make_sfc_simd! { $rng_name, $vector, $seed, 24, 11, 3 }
     };
-    ( 32bit: $rng_name:ident, $vector:ident ) => {
-        make_sfc_simd! { $rng_name, $vector, 21, 9, 3 }
+    ( 32bit: $rng_name:ident, $vector:ident, $seed:ty ) => {
+        make_sfc_simd! { $rng_name, $vector, $seed, 21, 9, 3 }
     };
-    ( 16bit: $rng_name:ident, $vector:ident ) => {
-        make_sfc_simd! { $rng_name, $vector, 6, 5, 3 }
+    ( 16bit: $rng_name:ident, $vector:ident, $seed:ty ) => {
+        make_sfc_simd! { $rng_name, $vector, $seed, 6, 5, 3 }
     };
 }
 
@@ -69,18 +71,18 @@ macro_rules! make_sfc_simd {
 // (multiple parameters could be used, though slow on older hardware)
 // (some counter-based techniques could be adapted)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                       Probability
-make_sfc_simd! { 64bit: Sfc64x2,  u64x2  } // ≈ 2^2  * l / 2^255 ≈ l * 2^-253
-make_sfc_simd! { 64bit: Sfc64x4,  u64x4  } // ≈ 4^2  * l / 2^255 ≈ l * 2^-251
-make_sfc_simd! { 64bit: Sfc64x8,  u64x8  } // ≈ 8^2  * l / 2^255 ≈ l * 2^-249
+// Listing probability of overlap somewhere:                                 Probability
+make_sfc_simd! { 64bit: Sfc64x2,  u64x2,  Seed384  } // ≈ 2^2  * l / 2^255 ≈ l * 2^-253
+make_sfc_simd! { 64bit: Sfc64x4,  u64x4,  Seed768  } // ≈ 4^2  * l / 2^255 ≈ l * 2^-251
+make_sfc_simd! { 64bit: Sfc64x8,  u64x8,  Seed1536 } // ≈ 8^2  * l / 2^255 ≈ l * 2^-249
 
-make_sfc_simd! { 32bit: Sfc32x2,  u32x2  } // ≈ 2^2  * l / 2^128 ≈ l * 2^-126
-make_sfc_simd! { 32bit: Sfc32x4,  u32x4  } // ≈ 4^2  * l / 2^128 ≈ l * 2^-124
-make_sfc_simd! { 32bit: Sfc32x8,  u32x8  } // ≈ 8^2  * l / 2^128 ≈ l * 2^-122
-make_sfc_simd! { 32bit: Sfc32x16, u32x16 } // ≈ 16^2 * l / 2^128 ≈ l * 2^-120
+make_sfc_simd! { 32bit: Sfc32x2,  u32x2,  [u8; 24] } // ≈ 2^2  * l / 2^128 ≈ l * 2^-126
+make_sfc_simd! { 32bit: Sfc32x4,  u32x4,  Seed384  } // ≈ 4^2  * l / 2^128 ≈ l * 2^-124
+make_sfc_simd! { 32bit: Sfc32x8,  u32x8,  Seed768  } // ≈ 8^2  * l / 2^128 ≈ l * 2^-122
+make_sfc_simd! { 32bit: Sfc32x16, u32x16, Seed1536 } // ≈ 16^2 * l / 2^128 ≈ l * 2^-120
 
-make_sfc_simd! { 16bit: Sfc16x2,  u16x2  } // ≈ 2^2  * l / 2^63  ≈ l * 2^-61
-make_sfc_simd! { 16bit: Sfc16x4,  u16x4  } // ≈ 4^2  * l / 2^63  ≈ l * 2^-59
-make_sfc_simd! { 16bit: Sfc16x8,  u16x8  } // ≈ 8^2  * l / 2^63  ≈ l * 2^-57
-make_sfc_simd! { 16bit: Sfc16x16, u16x16 } // ≈ 16^2 * l / 2^63  ≈ l * 2^-55
-make_sfc_simd! { 16bit: Sfc16x32, u16x32 } // ≈ 32^2 * l / 2^63  ≈ l * 2^-52
+make_sfc_simd! { 16bit: Sfc16x2,  u16x2,  [u8; 12] } // ≈ 2^2  * l / 2^63  ≈ l * 2^-61
+make_sfc_simd! { 16bit: Sfc16x4,  u16x4,  [u8; 24] } // ≈ 4^2  * l / 2^63  ≈ l * 2^-59
+make_sfc_simd! { 16bit: Sfc16x8,  u16x8,  Seed384  } // ≈ 8^2  * l / 2^63  ≈ l * 2^-57
+make_sfc_simd! { 16bit: Sfc16x16, u16x16, Seed768  } // ≈ 16^2 * l / 2^63  ≈ l * 2^-55
+make_sfc_simd! { 16bit: Sfc16x32, u16x32, Seed1536 } // ≈ 32^2 * l / 2^63  ≈ l * 2^-52
@@ -346,7 +346,8 @@ macro_rules! sfc_alt_l {
 }
 
 macro_rules! make_sfc {
-    ($rng_name:ident, $version:ident, $vector:ident, constants: $sh1:expr, $sh2:expr, $sh3:expr, e1: $e_sh:expr, e2: $e_sh1:expr, $e_sh2:expr) => {
+    ($rng_name:ident, $version:ident, $vector:ident, $seed:ty, constants: $sh1:expr, $sh2:expr, $sh3:expr, e1: $e_sh:expr, e2: $e_sh1:expr, $e_sh2:expr) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             a: $vector,
             b: $vector,
@@ -358,85 +359,86 @@ macro_rules! make_sfc {
             counter2: $vector,
         }
 
+        impl_debug! { $rng_name { a, b, c, counter, counter2 } }
+
+        impl_serde! { $rng_name { a, b, c, counter, counter2 } }
+
         $version!($rng_name, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2);
 
         impl_rngcore! { $rng_name }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!();
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 3);
 
-            fn from_rng<R: RngCore>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 3];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
-
-                Ok(Self {
+                Self {
                     a: seed[0],
                     b: seed[1],
                     c: seed[2],
                     counter: $vector::splat(0),
                     counter2: $vector::splat(1),
-                })
+                }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 
 
-    ( versions: $vector:ident, constants: $sh1:expr, $sh2:expr, $sh3:expr, e1: $e_sh:expr, e2: $e_sh1:expr, $e_sh2:expr,
+    ( versions: $vector:ident, $seed:ty, constants: $sh1:expr, $sh2:expr, $sh3:expr, e1: $e_sh:expr, e2: $e_sh1:expr, $e_sh2:expr,
         $name_a:ident, $name_b:ident, $name_c:ident, $name_d:ident, $name_e:ident, $name_f:ident,
         $name_g:ident, $name_h:ident, $name_i:ident, $name_j:ident, $name_k:ident, $name_l:ident,
     ) => {
-        make_sfc! { $name_a, sfc_alt_a, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_b, sfc_alt_b, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_c, sfc_alt_c, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_d, sfc_alt_d, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_e, sfc_alt_e, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_f, sfc_alt_f, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_g, sfc_alt_g, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_h, sfc_alt_h, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_i, sfc_alt_i, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_j, sfc_alt_j, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_k, sfc_alt_k, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
-        make_sfc! { $name_l, sfc_alt_l, $vector, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_a, sfc_alt_a, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_b, sfc_alt_b, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_c, sfc_alt_c, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_d, sfc_alt_d, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_e, sfc_alt_e, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_f, sfc_alt_f, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_g, sfc_alt_g, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_h, sfc_alt_h, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_i, sfc_alt_i, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_j, sfc_alt_j, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_k, sfc_alt_k, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
+        make_sfc! { $name_l, sfc_alt_l, $vector, $seed, constants: $sh1, $sh2, $sh3, e1: $e_sh, e2: $e_sh1, $e_sh2 }
     };
 
-    ( 64bit: $vector:ident, $($rng_name:ident,)+) => {
-        make_sfc! { versions: $vector, constants: 25, 12, 3, e1: 43, e2: 48, 3, $($rng_name,)+ }
+    ( 64bit: $vector:ident, $seed:ty, $($rng_name:ident,)+) => {
+        make_sfc! { versions: $vector, $seed, constants: 25, 12, 3, e1: 43, e2: 48, 3, $($rng_name,)+ }
     };
-    ( 32bit: $vector:ident, $($rng_name:ident,)+) => {
-        make_sfc! { versions: $vector, constants: 25, 8, 3, e1: 23, e2: 14, 3, $($rng_name,)+ }
+    ( 32bit: $vector:ident, $seed:ty, $($rng_name:ident,)+) => {
+        make_sfc! { versions: $vector, $seed, constants: 25, 8, 3, e1: 23, e2: 14, 3, $($rng_name,)+ }
     };
-    ( 16bit: $vector:ident, $($rng_name:ident,)+) => {
-        make_sfc! { versions: $vector, constants: 7, 3, 2, e1: 11, e2: 9, 3, $($rng_name,)+ }
+    ( 16bit: $vector:ident, $seed:ty, $($rng_name:ident,)+) => {
+        make_sfc! { versions: $vector, $seed, constants: 7, 3, 2, e1: 11, e2: 9, 3, $($rng_name,)+ }
     };
-    ( 8bit: $vector:ident, $($rng_name:ident,)+) => {
-        make_sfc! { versions: $vector, constants: 3, 2, 1, e1: 0, e2: 5, 2, $($rng_name,)+ }
+    ( 8bit: $vector:ident, $seed:ty, $($rng_name:ident,)+) => {
+        make_sfc! { versions: $vector, $seed, constants: 3, 2, 1, e1: 0, e2: 5, 2, $($rng_name,)+ }
     };
 }
 
 // WARNING: must be in proper order
 
-make_sfc! { 64bit: u64x2, SfcAlt64x2a, SfcAlt64x2b, SfcAlt64x2c, SfcAlt64x2d, SfcAlt64x2e, SfcAlt64x2f, SfcAlt64x2g, SfcAlt64x2h, SfcAlt64x2i, SfcAlt64x2j, SfcAlt64x2k, SfcAlt64x2l, }
-make_sfc! { 64bit: u64x4, SfcAlt64x4a, SfcAlt64x4b, SfcAlt64x4c, SfcAlt64x4d, SfcAlt64x4e, SfcAlt64x4f, SfcAlt64x4g, SfcAlt64x4h, SfcAlt64x4i, SfcAlt64x4j, SfcAlt64x4k, SfcAlt64x4l, }
-make_sfc! { 64bit: u64x8, SfcAlt64x8a, SfcAlt64x8b, SfcAlt64x8c, SfcAlt64x8d, SfcAlt64x8e, SfcAlt64x8f, SfcAlt64x8g, SfcAlt64x8h, SfcAlt64x8i, SfcAlt64x8j, SfcAlt64x8k, SfcAlt64x8l, }
-
-make_sfc! { 32bit: u32x2, SfcAlt32x2a, SfcAlt32x2b, SfcAlt32x2c, SfcAlt32x2d, SfcAlt32x2e, SfcAlt32x2f, SfcAlt32x2g, SfcAlt32x2h, SfcAlt32x2i, SfcAlt32x2j, SfcAlt32x2k, SfcAlt32x2l, }
-make_sfc! { 32bit: u32x4, SfcAlt32x4a, SfcAlt32x4b, SfcAlt32x4c, SfcAlt32x4d, SfcAlt32x4e, SfcAlt32x4f, SfcAlt32x4g, SfcAlt32x4h, SfcAlt32x4i, SfcAlt32x4j, SfcAlt32x4k, SfcAlt32x4l, }
-make_sfc! { 32bit: u32x8, SfcAlt32x8a, SfcAlt32x8b, SfcAlt32x8c, SfcAlt32x8d, SfcAlt32x8e, SfcAlt32x8f, SfcAlt32x8g, SfcAlt32x8h, SfcAlt32x8i, SfcAlt32x8j, SfcAlt32x8k, SfcAlt32x8l, }
-make_sfc! { 32bit: u32x16, SfcAlt32x16a, SfcAlt32x16b, SfcAlt32x16c, SfcAlt32x16d, SfcAlt32x16e, SfcAlt32x16f, SfcAlt32x16g, SfcAlt32x16h, SfcAlt32x16i, SfcAlt32x16j, SfcAlt32x16k, SfcAlt32x16l, }
-
-make_sfc! { 16bit: u16x2, SfcAlt16x2a, SfcAlt16x2b, SfcAlt16x2c, SfcAlt16x2d, SfcAlt16x2e, SfcAlt16x2f, SfcAlt16x2g, SfcAlt16x2h, SfcAlt16x2i, SfcAlt16x2j, SfcAlt16x2k, SfcAlt16x2l, }
-make_sfc! { 16bit: u16x4, SfcAlt16x4a, SfcAlt16x4b, SfcAlt16x4c, SfcAlt16x4d, SfcAlt16x4e, SfcAlt16x4f, SfcAlt16x4g, SfcAlt16x4h, SfcAlt16x4i, SfcAlt16x4j, SfcAlt16x4k, SfcAlt16x4l, }
-make_sfc! { 16bit: u16x8, SfcAlt16x8a, SfcAlt16x8b, SfcAlt16x8c, SfcAlt16x8d, SfcAlt16x8e, SfcAlt16x8f, SfcAlt16x8g, SfcAlt16x8h, SfcAlt16x8i, SfcAlt16x8j, SfcAlt16x8k, SfcAlt16x8l, }
-make_sfc! { 16bit: u16x16, SfcAlt16x16a, SfcAlt16x16b, SfcAlt16x16c, SfcAlt16x16d, SfcAlt16x16e, SfcAlt16x16f, SfcAlt16x16g, SfcAlt16x16h, SfcAlt16x16i, SfcAlt16x16j, SfcAlt16x16k, SfcAlt16x16l, }
-make_sfc! { 16bit: u16x32, SfcAlt16x32a, SfcAlt16x32b, SfcAlt16x32c, SfcAlt16x32d, SfcAlt16x32e, SfcAlt16x32f, SfcAlt16x32g, SfcAlt16x32h, SfcAlt16x32i, SfcAlt16x32j, SfcAlt16x32k, SfcAlt16x32l, }
-
-make_sfc! { 8bit: u8x2, SfcAlt8x2a, SfcAlt8x2b, SfcAlt8x2c, SfcAlt8x2d, SfcAlt8x2e, SfcAlt8x2f, SfcAlt8x2g, SfcAlt8x2h, SfcAlt8x2i, SfcAlt8x2j, SfcAlt8x2k, SfcAlt8x2l, }
-make_sfc! { 8bit: u8x4, SfcAlt8x4a, SfcAlt8x4b, SfcAlt8x4c, SfcAlt8x4d, SfcAlt8x4e, SfcAlt8x4f, SfcAlt8x4g, SfcAlt8x4h, SfcAlt8x4i, SfcAlt8x4j, SfcAlt8x4k, SfcAlt8x4l, }
-make_sfc! { 8bit: u8x8, SfcAlt8x8a, SfcAlt8x8b, SfcAlt8x8c, SfcAlt8x8d, SfcAlt8x8e, SfcAlt8x8f, SfcAlt8x8g, SfcAlt8x8h, SfcAlt8x8i, SfcAlt8x8j, SfcAlt8x8k, SfcAlt8x8l, }
-make_sfc! { 8bit: u8x16, SfcAlt8x16a, SfcAlt8x16b, SfcAlt8x16c, SfcAlt8x16d, SfcAlt8x16e, SfcAlt8x16f, SfcAlt8x16g, SfcAlt8x16h, SfcAlt8x16i, SfcAlt8x16j, SfcAlt8x16k, SfcAlt8x16l, }
-make_sfc! { 8bit: u8x32, SfcAlt8x32a, SfcAlt8x32b, SfcAlt8x32c, SfcAlt8x32d, SfcAlt8x32e, SfcAlt8x32f, SfcAlt8x32g, SfcAlt8x32h, SfcAlt8x32i, SfcAlt8x32j, SfcAlt8x32k, SfcAlt8x32l, }
-make_sfc! { 8bit: u8x64, SfcAlt8x64a, SfcAlt8x64b, SfcAlt8x64c, SfcAlt8x64d, SfcAlt8x64e, SfcAlt8x64f, SfcAlt8x64g, SfcAlt8x64h, SfcAlt8x64i, SfcAlt8x64j, SfcAlt8x64k, SfcAlt8x64l, }
+make_sfc! { 64bit: u64x2, Seed384, SfcAlt64x2a, SfcAlt64x2b, SfcAlt64x2c, SfcAlt64x2d, SfcAlt64x2e, SfcAlt64x2f, SfcAlt64x2g, SfcAlt64x2h, SfcAlt64x2i, SfcAlt64x2j, SfcAlt64x2k, SfcAlt64x2l, }
+make_sfc! { 64bit: u64x4, Seed768, SfcAlt64x4a, SfcAlt64x4b, SfcAlt64x4c, SfcAlt64x4d, SfcAlt64x4e, SfcAlt64x4f, SfcAlt64x4g, SfcAlt64x4h, SfcAlt64x4i, SfcAlt64x4j, SfcAlt64x4k, SfcAlt64x4l, }
+make_sfc! { 64bit: u64x8, Seed1536, SfcAlt64x8a, SfcAlt64x8b, SfcAlt64x8c, SfcAlt64x8d, SfcAlt64x8e, SfcAlt64x8f, SfcAlt64x8g, SfcAlt64x8h, SfcAlt64x8i, SfcAlt64x8j, SfcAlt64x8k, SfcAlt64x8l, }
+
+make_sfc! { 32bit: u32x2, [u8; 24], SfcAlt32x2a, SfcAlt32x2b, SfcAlt32x2c, SfcAlt32x2d, SfcAlt32x2e, SfcAlt32x2f, SfcAlt32x2g, SfcAlt32x2h, SfcAlt32x2i, SfcAlt32x2j, SfcAlt32x2k, SfcAlt32x2l, }
+make_sfc! { 32bit: u32x4, Seed384, SfcAlt32x4a, SfcAlt32x4b, SfcAlt32x4c, SfcAlt32x4d, SfcAlt32x4e, SfcAlt32x4f, SfcAlt32x4g, SfcAlt32x4h, SfcAlt32x4i, SfcAlt32x4j, SfcAlt32x4k, SfcAlt32x4l, }
+make_sfc! { 32bit: u32x8, Seed768, SfcAlt32x8a, SfcAlt32x8b, SfcAlt32x8c, SfcAlt32x8d, SfcAlt32x8e, SfcAlt32x8f, SfcAlt32x8g, SfcAlt32x8h, SfcAlt32x8i, SfcAlt32x8j, SfcAlt32x8k, SfcAlt32x8l, }
+make_sfc! { 32bit: u32x16, Seed1536, SfcAlt32x16a, SfcAlt32x16b, SfcAlt32x16c, SfcAlt32x16d, SfcAlt32x16e, SfcAlt32x16f, SfcAlt32x16g, SfcAlt32x16h, SfcAlt32x16i, SfcAlt32x16j, SfcAlt32x16k, SfcAlt32x16l, }
+
+make_sfc! { 16bit: u16x2, [u8; 12], SfcAlt16x2a, SfcAlt16x2b, SfcAlt16x2c, SfcAlt16x2d, SfcAlt16x2e, SfcAlt16x2f, SfcAlt16x2g, SfcAlt16x2h, SfcAlt16x2i, SfcAlt16x2j, SfcAlt16x2k, SfcAlt16x2l, }
+make_sfc! { 16bit: u16x4, [u8; 24], SfcAlt16x4a, SfcAlt16x4b, SfcAlt16x4c, SfcAlt16x4d, SfcAlt16x4e, SfcAlt16x4f, SfcAlt16x4g, SfcAlt16x4h, SfcAlt16x4i, SfcAlt16x4j, SfcAlt16x4k, SfcAlt16x4l, }
+make_sfc! { 16bit: u16x8, Seed384, SfcAlt16x8a, SfcAlt16x8b, SfcAlt16x8c, SfcAlt16x8d, SfcAlt16x8e, SfcAlt16x8f, SfcAlt16x8g, SfcAlt16x8h, SfcAlt16x8i, SfcAlt16x8j, SfcAlt16x8k, SfcAlt16x8l, }
+make_sfc! { 16bit: u16x16, Seed768, SfcAlt16x16a, SfcAlt16x16b, SfcAlt16x16c, SfcAlt16x16d, SfcAlt16x16e, SfcAlt16x16f, SfcAlt16x16g, SfcAlt16x16h, SfcAlt16x16i, SfcAlt16x16j, SfcAlt16x16k, SfcAlt16x16l, }
+make_sfc! { 16bit: u16x32, Seed1536, SfcAlt16x32a, SfcAlt16x32b, SfcAlt16x32c, SfcAlt16x32d, SfcAlt16x32e, SfcAlt16x32f, SfcAlt16x32g, SfcAlt16x32h, SfcAlt16x32i, SfcAlt16x32j, SfcAlt16x32k, SfcAlt16x32l, }
+
+make_sfc! { 8bit: u8x2, [u8; 6], SfcAlt8x2a, SfcAlt8x2b, SfcAlt8x2c, SfcAlt8x2d, SfcAlt8x2e, SfcAlt8x2f, SfcAlt8x2g, SfcAlt8x2h, SfcAlt8x2i, SfcAlt8x2j, SfcAlt8x2k, SfcAlt8x2l, }
+make_sfc! { 8bit: u8x4, [u8; 12], SfcAlt8x4a, SfcAlt8x4b, SfcAlt8x4c, SfcAlt8x4d, SfcAlt8x4e, SfcAlt8x4f, SfcAlt8x4g, SfcAlt8x4h, SfcAlt8x4i, SfcAlt8x4j, SfcAlt8x4k, SfcAlt8x4l, }
+make_sfc! { 8bit: u8x8, [u8; 24], SfcAlt8x8a, SfcAlt8x8b, SfcAlt8x8c, SfcAlt8x8d, SfcAlt8x8e, SfcAlt8x8f, SfcAlt8x8g, SfcAlt8x8h, SfcAlt8x8i, SfcAlt8x8j, SfcAlt8x8k, SfcAlt8x8l, }
+make_sfc! { 8bit: u8x16, Seed384, SfcAlt8x16a, SfcAlt8x16b, SfcAlt8x16c, SfcAlt8x16d, SfcAlt8x16e, SfcAlt8x16f, SfcAlt8x16g, SfcAlt8x16h, SfcAlt8x16i, SfcAlt8x16j, SfcAlt8x16k, SfcAlt8x16l, }
+make_sfc! { 8bit: u8x32, Seed768, SfcAlt8x32a, SfcAlt8x32b, SfcAlt8x32c, SfcAlt8x32d, SfcAlt8x32e, SfcAlt8x32f, SfcAlt8x32g, SfcAlt8x32h, SfcAlt8x32i, SfcAlt8x32j, SfcAlt8x32k, SfcAlt8x32l, }
+make_sfc! { 8bit: u8x64, Seed1536, SfcAlt8x64a, SfcAlt8x64b, SfcAlt8x64c, SfcAlt8x64d, SfcAlt8x64e, SfcAlt8x64f, SfcAlt8x64g, SfcAlt8x64h, SfcAlt8x64i, SfcAlt8x64j, SfcAlt8x64k, SfcAlt8x64l, }
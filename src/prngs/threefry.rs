@@ -0,0 +1,304 @@
+// Each lane is its own Random123 stream with its own key and counter. Words
+// of a block are spread over vectors, so every rotate is by the same amount
+// on all lanes and byte-aligned ones become shuffles through `RotateOpt`.
+
+use rng_impl::*;
+
+/// Key schedule parity.
+const C240: u64 = 0x1BD11BDAA9FC1A22;
+
+const ROT_2X64: [usize; 8] = [16, 42, 12, 31, 16, 32, 24, 21];
+
+#[rustfmt::skip]
+const ROT_4X64: [[usize; 2]; 8] = [
+    [14, 16], [52, 57], [23, 40], [5, 37],
+    [25, 33], [46, 12], [58, 22], [32, 32],
+];
+
+macro_rules! impl_threefry_common {
+    ($rng_name:ident, $vector:ident, $seed:ty, $words:expr, $rounds:expr) => {
+        /// Threefry from [Random123], one stream per lane.
+        ///
+        /// - Source: Salmon et al., "Parallel Random Numbers: As Easy as 1, 2,
+        ///   3" (2011)
+        /// - Cycle Length: 2<sup>64 * words</sup> per stream
+        /// - BigCrush: passed
+        ///
+        /// Only adds, rotates and xors, so it is fast without AES-NI.
+        ///
+        /// [Random123]: http://www.deshawresearch.com/resources_random123.html
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $rng_name {
+            ctr: [$vector; $words],
+            key: [$vector; $words],
+            buffer: [$vector; $words],
+            idx: u8,
+        }
+
+        impl_debug! { $rng_name { ctr, key, buffer, idx } }
+
+        impl_serde! {
+            $rng_name { ctr, key, buffer, idx } where |rng| {
+                if rng.idx as usize <= $words {
+                    Ok(())
+                } else {
+                    Err("a buffer index past the block")
+                }
+            }
+        }
+
+        impl SimdRng for $rng_name {
+            type Result = $vector;
+
+            /// Outputs the words of a block one at a time.
+            #[inline(always)]
+            fn generate(&mut self) -> $vector {
+                if self.idx as usize == $words {
+                    self.buffer = Self::block(self.ctr, self.key, $rounds);
+                    self.idx = 0;
+
+                    let mut carry = $vector::splat(1);
+                    for word in self.ctr.iter_mut() {
+                        *word += carry;
+                        carry = word.eq($vector::splat(0)).select(carry, $vector::splat(0));
+                    }
+                }
+
+                let result = self.buffer[self.idx as usize];
+                self.idx += 1;
+                result
+            }
+        }
+
+        impl_rngcore! { $rng_name }
+
+        impl SeedableRng for $rng_name {
+            type Seed = $seed;
+
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let s = read_seed!(seed, $vector; 2 * $words);
+
+                let mut key = [$vector::default(); $words];
+                let mut ctr = [$vector::default(); $words];
+                key.copy_from_slice(&s[..$words]);
+                ctr.copy_from_slice(&s[$words..]);
+
+                Self {
+                    ctr,
+                    key,
+                    buffer: [$vector::default(); $words],
+                    idx: $words,
+                }
+            }
+
+            impl_seed_from_u64! {}
+        }
+    };
+}
+
+macro_rules! make_threefry2x64 {
+    ($rng_name:ident, $vector:ident, $seed:ty, $rounds:expr) => {
+        impl_threefry_common! { $rng_name, $vector, $seed, 2, $rounds }
+
+        impl $rng_name {
+            #[inline(always)]
+            fn block(ctr: [$vector; 2], key: [$vector; 2], rounds: usize) -> [$vector; 2] {
+                let ks = [key[0], key[1], key[0] ^ key[1] ^ C240];
+                let mut x = [ctr[0] + ks[0], ctr[1] + ks[1]];
+
+                for round in 0..rounds {
+                    x[0] += x[1];
+                    x[1] = x[1].rotate_left_opt(ROT_2X64[round % 8]) ^ x[0];
+
+                    // key injection every 4 rounds
+                    if round % 4 == 3 {
+                        let s = (round + 1) / 4;
+                        x[0] += ks[s % 3];
+                        x[1] += ks[(s + 1) % 3] + s as u64;
+                    }
+                }
+
+                x
+            }
+        }
+    };
+}
+
+macro_rules! make_threefry4x64 {
+    ($rng_name:ident, $vector:ident, $seed:ty, $rounds:expr) => {
+        impl_threefry_common! { $rng_name, $vector, $seed, 4, $rounds }
+
+        impl $rng_name {
+            #[inline(always)]
+            fn block(ctr: [$vector; 4], key: [$vector; 4], rounds: usize) -> [$vector; 4] {
+                let parity = key.iter().fold($vector::splat(C240), |acc, &k| acc ^ k);
+                let ks = [key[0], key[1], key[2], key[3], parity];
+                let mut x = [
+                    ctr[0] + ks[0],
+                    ctr[1] + ks[1],
+                    ctr[2] + ks[2],
+                    ctr[3] + ks[3],
+                ];
+
+                for round in 0..rounds {
+                    let [r0, r1] = ROT_4X64[round % 8];
+
+                    // the odd rounds permute words 1 and 3
+                    let (b, d) = if round % 2 == 0 { (1, 3) } else { (3, 1) };
+                    x[0] += x[b];
+                    x[b] = x[b].rotate_left_opt(r0) ^ x[0];
+                    x[2] += x[d];
+                    x[d] = x[d].rotate_left_opt(r1) ^ x[2];
+
+                    // key injection every 4 rounds
+                    if round % 4 == 3 {
+                        let s = (round + 1) / 4;
+                        for (i, word) in x.iter_mut().enumerate() {
+                            *word += ks[(s + i) % 5];
+                        }
+                        x[3] += s as u64;
+                    }
+                }
+
+                x
+            }
+        }
+    };
+}
+
+// The Random123 defaults are 20 rounds, 13 is the fewest which is
+// "Crush-resistant".
+#[rustfmt::skip]
+make_threefry2x64! { Threefry2x64R13x2, u64x2, Seed512,  13 }
+#[rustfmt::skip]
+make_threefry2x64! { Threefry2x64R13x4, u64x4, Seed1024, 13 }
+#[rustfmt::skip]
+make_threefry2x64! { Threefry2x64R13x8, u64x8, Seed2048, 13 }
+#[rustfmt::skip]
+make_threefry2x64! { Threefry2x64R20x2, u64x2, Seed512,  20 }
+#[rustfmt::skip]
+make_threefry2x64! { Threefry2x64R20x4, u64x4, Seed1024, 20 }
+#[rustfmt::skip]
+make_threefry2x64! { Threefry2x64R20x8, u64x8, Seed2048, 20 }
+
+#[rustfmt::skip]
+make_threefry4x64! { Threefry4x64R13x2, u64x2, Seed1024, 13 }
+#[rustfmt::skip]
+make_threefry4x64! { Threefry4x64R13x4, u64x4, Seed2048, 13 }
+#[rustfmt::skip]
+make_threefry4x64! { Threefry4x64R13x8, u64x8, Seed4096, 13 }
+#[rustfmt::skip]
+make_threefry4x64! { Threefry4x64R20x2, u64x2, Seed1024, 20 }
+#[rustfmt::skip]
+make_threefry4x64! { Threefry4x64R20x4, u64x4, Seed2048, 20 }
+#[rustfmt::skip]
+make_threefry4x64! { Threefry4x64R20x8, u64x8, Seed4096, 20 }
+
+/// The Threefry2x64 bijection with `rounds` rounds, as Random123's
+/// `threefry2x64_R`.
+pub fn threefry2x64_r(rounds: usize, ctr: u64x2, key: u64x2) -> u64x2 {
+    let splat = |x: u64x2, i| u64x2::splat(x.extract(i));
+    let x = Threefry2x64R20x2::block(
+        [splat(ctr, 0), splat(ctr, 1)],
+        [splat(key, 0), splat(key, 1)],
+        rounds,
+    );
+    u64x2::new(x[0].extract(0), x[1].extract(0))
+}
+
+/// The Threefry4x64 bijection with `rounds` rounds, as Random123's
+/// `threefry4x64_R`.
+pub fn threefry4x64_r(rounds: usize, ctr: u64x4, key: u64x4) -> u64x4 {
+    let splat = |x: u64x4, i| u64x2::splat(x.extract(i));
+    let x = Threefry4x64R20x2::block(
+        [splat(ctr, 0), splat(ctr, 1), splat(ctr, 2), splat(ctr, 3)],
+        [splat(key, 0), splat(key, 1), splat(key, 2), splat(key, 3)],
+        rounds,
+    );
+    u64x4::new(x[0].extract(0), x[1].extract(0), x[2].extract(0), x[3].extract(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PI: [u64; 8] = [
+        0x243f6a8885a308d3,
+        0x13198a2e03707344,
+        0xa4093822299f31d0,
+        0x082efa98ec4e6c89,
+        0x452821e638d01377,
+        0xbe5466cf34e90c6c,
+        0xc0ac29b7c97c50dd,
+        0x3f84d5b5b5470917,
+    ];
+
+    // Random123's kat_vectors
+    #[test]
+    fn threefry2x64_kat() {
+        let kat = |rounds, ctr: [u64; 2], key: [u64; 2], expected: [u64; 2]| {
+            let ctr = u64x2::new(ctr[0], ctr[1]);
+            let key = u64x2::new(key[0], key[1]);
+            let expected = u64x2::new(expected[0], expected[1]);
+            assert_eq!(threefry2x64_r(rounds, ctr, key), expected);
+        };
+
+        let (pi_ctr, pi_key) = ([PI[0], PI[1]], [PI[2], PI[3]]);
+
+        kat(13, [0; 2], [0; 2], [0xf167b032c3b480bd, 0xe91f9fee4b7a6fb5]);
+        kat(13, [!0; 2], [!0; 2], [0xccdec5c917a874b1, 0x4df53abca26ceb01]);
+        kat(13, pi_ctr, pi_key, [0xc3aac71561042993, 0x3fe7ae8801aff316]);
+        kat(20, [0; 2], [0; 2], [0xc2b6e3a8c2c69865, 0x6f81ed42f350084d]);
+        kat(20, [!0; 2], [!0; 2], [0xe02cb7c4d95d277a, 0xd06633d0893b8b68]);
+        kat(20, pi_ctr, pi_key, [0x263c7d30bb0f0af1, 0x56be8361d3311526]);
+    }
+
+    #[test]
+    fn threefry4x64_kat() {
+        let kat = |rounds, ctr: [u64; 4], key: [u64; 4], expected: [u64; 4]| {
+            let vector = |x: [u64; 4]| u64x4::new(x[0], x[1], x[2], x[3]);
+            assert_eq!(threefry4x64_r(rounds, vector(ctr), vector(key)), vector(expected));
+        };
+
+        let (pi_ctr, pi_key) = ([PI[0], PI[1], PI[2], PI[3]], [PI[4], PI[5], PI[6], PI[7]]);
+
+        #[rustfmt::skip]
+        let expected = [
+            [0x4071fabee1dc8e05, 0x02ed3113695c9c62, 0x397311b5b89f9d49, 0xe21292c3258024bc],
+            [0x7eaed935479722b5, 0x90994358c429f31c, 0x496381083e07a75b, 0x627ed0d746821121],
+            [0x4361288ef9c1900c, 0x8717291521782833, 0x0d19db18c20cf47e, 0xa0b41d63ac8581e5],
+            [0x09218ebde6c85537, 0x55941f5266d86105, 0x4bd25e16282434dc, 0xee29ec846bd2e40b],
+            [0x29c24097942bba1b, 0x0371bbfb0f6f4e11, 0x3c231ffa33f83a1c, 0xcd29113fde32d168],
+            [0xbb893fd42eac50eb, 0x7ca8b22905f3443a, 0xe204b8dcb4daace7, 0x3e1070a2327bfc09],
+        ];
+
+        kat(13, [0; 4], [0; 4], expected[0]);
+        kat(13, [!0; 4], [!0; 4], expected[1]);
+        kat(13, pi_ctr, pi_key, expected[2]);
+        kat(20, [0; 4], [0; 4], expected[3]);
+        kat(20, [!0; 4], [!0; 4], expected[4]);
+        kat(20, pi_ctr, pi_key, expected[5]);
+    }
+
+    #[test]
+    fn threefry_streams() {
+        let mut rng = Threefry2x64R20x4::seed_from_u64(0);
+        // the second lane's counter carries into its high word
+        rng.ctr[0] = rng.ctr[0].replace(1, !0);
+        let (ctr, key) = (rng.ctr, rng.key);
+
+        for i in 0..2 {
+            let words = [rng.generate(), rng.generate()];
+            for lane in 0..4 {
+                let c = u128::from(ctr[0].extract(lane)) | u128::from(ctr[1].extract(lane)) << 64;
+                let c = c.wrapping_add(i);
+                let c = u64x2::new(c as u64, (c >> 64) as u64);
+                let k = u64x2::new(key[0].extract(lane), key[1].extract(lane));
+
+                let block = threefry2x64_r(20, c, k);
+                assert_eq!(words[0].extract(lane), block.extract(0));
+                assert_eq!(words[1].extract(lane), block.extract(1));
+            }
+        }
+    }
+}
@@ -145,7 +145,8 @@ macro_rules! vf_g {
 }
 
 macro_rules! make_vf {
-    ($rng_name:ident, $version:ident, $vector:ident, $rot:expr, $shr:expr, $shl:expr) => {
+    ($rng_name:ident, $version:ident, $vector:ident, $seed:ty, $rot:expr, $shr:expr, $shl:expr) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             a: $vector,
             b: $vector,
@@ -153,77 +154,78 @@ macro_rules! make_vf {
             c: $vector,
         }
 
+        impl_debug! { $rng_name { a, b, c } }
+
+        impl_serde! { $rng_name { a, b, c } }
+
         $version!($rng_name, $vector, $rot, $shr, $shl);
 
         impl_rngcore! { $rng_name }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!();
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seed = read_seed!(seed, $vector; 3);
 
-            fn from_rng<R: RngCore>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 3];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
-
-                Ok(Self {
+                Self {
                     a: seed[0],
                     b: seed[1],
                     c: seed[2],
-                })
+                }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 
 
-    ( versions: $vector:ident, $rot:expr, $shr:expr, $shl:expr,
+    ( versions: $vector:ident, $seed:ty, $rot:expr, $shr:expr, $shl:expr,
         $name_a:ident, $name_b:ident, $name_c:ident, $name_d:ident, $name_e:ident, $name_f:ident, $name_g:ident,
     ) => {
-        make_vf! { $name_a, vf_a, $vector, $rot, $shr, $shl }
-        make_vf! { $name_b, vf_b, $vector, $rot, $shr, $shl }
-        make_vf! { $name_c, vf_c, $vector, $rot, $shr, $shl }
-        make_vf! { $name_d, vf_d, $vector, $rot, $shr, $shl }
-        make_vf! { $name_e, vf_e, $vector, $rot, $shr, $shl }
-        make_vf! { $name_f, vf_f, $vector, $rot, $shr, $shl }
-        make_vf! { $name_g, vf_g, $vector, $rot, $shr, $shl }
+        make_vf! { $name_a, vf_a, $vector, $seed, $rot, $shr, $shl }
+        make_vf! { $name_b, vf_b, $vector, $seed, $rot, $shr, $shl }
+        make_vf! { $name_c, vf_c, $vector, $seed, $rot, $shr, $shl }
+        make_vf! { $name_d, vf_d, $vector, $seed, $rot, $shr, $shl }
+        make_vf! { $name_e, vf_e, $vector, $seed, $rot, $shr, $shl }
+        make_vf! { $name_f, vf_f, $vector, $seed, $rot, $shr, $shl }
+        make_vf! { $name_g, vf_g, $vector, $seed, $rot, $shr, $shl }
     };
 
-    ( 64bit: $vector:ident, $($rng_name:ident,)+) => {
-        make_vf! { versions: $vector, 29, 9, 3, $($rng_name,)+ }
+    ( 64bit: $vector:ident, $seed:ty, $($rng_name:ident,)+) => {
+        make_vf! { versions: $vector, $seed, 29, 9, 3, $($rng_name,)+ }
     };
-    ( 32bit: $vector:ident, $($rng_name:ident,)+) => {
-        make_vf! { versions: $vector, 13, 7, 3, $($rng_name,)+ }
+    ( 32bit: $vector:ident, $seed:ty, $($rng_name:ident,)+) => {
+        make_vf! { versions: $vector, $seed, 13, 7, 3, $($rng_name,)+ }
     };
-    ( 16bit: $vector:ident, $($rng_name:ident,)+) => {
-        make_vf! { versions: $vector, 7, 3, 2, $($rng_name,)+ }
+    ( 16bit: $vector:ident, $seed:ty, $($rng_name:ident,)+) => {
+        make_vf! { versions: $vector, $seed, 7, 3, 2, $($rng_name,)+ }
     };
-    ( 8bit: $vector:ident, $($rng_name:ident,)+) => {
-        make_vf! { versions: $vector, 3, 2, 2, $($rng_name,)+ }
+    ( 8bit: $vector:ident, $seed:ty, $($rng_name:ident,)+) => {
+        make_vf! { versions: $vector, $seed, 3, 2, 2, $($rng_name,)+ }
     };
 }
 
 // WARNING: must be in proper order
 
-make_vf! { 64bit: u64x2, VeryFast64x2a, VeryFast64x2b, VeryFast64x2c, VeryFast64x2d, VeryFast64x2e, VeryFast64x2f, VeryFast64x2g, }
-make_vf! { 64bit: u64x4, VeryFast64x4a, VeryFast64x4b, VeryFast64x4c, VeryFast64x4d, VeryFast64x4e, VeryFast64x4f, VeryFast64x4g, }
-make_vf! { 64bit: u64x8, VeryFast64x8a, VeryFast64x8b, VeryFast64x8c, VeryFast64x8d, VeryFast64x8e, VeryFast64x8f, VeryFast64x8g, }
-
-make_vf! { 32bit: u32x2, VeryFast32x2a, VeryFast32x2b, VeryFast32x2c, VeryFast32x2d, VeryFast32x2e, VeryFast32x2f, VeryFast32x2g, }
-make_vf! { 32bit: u32x4, VeryFast32x4a, VeryFast32x4b, VeryFast32x4c, VeryFast32x4d, VeryFast32x4e, VeryFast32x4f, VeryFast32x4g, }
-make_vf! { 32bit: u32x8, VeryFast32x8a, VeryFast32x8b, VeryFast32x8c, VeryFast32x8d, VeryFast32x8e, VeryFast32x8f, VeryFast32x8g, }
-make_vf! { 32bit: u32x16, VeryFast32x16a, VeryFast32x16b, VeryFast32x16c, VeryFast32x16d, VeryFast32x16e, VeryFast32x16f, VeryFast32x16g, }
-
-make_vf! { 16bit: u16x2, VeryFast16x2a, VeryFast16x2b, VeryFast16x2c, VeryFast16x2d, VeryFast16x2e, VeryFast16x2f, VeryFast16x2g, }
-make_vf! { 16bit: u16x4, VeryFast16x4a, VeryFast16x4b, VeryFast16x4c, VeryFast16x4d, VeryFast16x4e, VeryFast16x4f, VeryFast16x4g, }
-make_vf! { 16bit: u16x8, VeryFast16x8a, VeryFast16x8b, VeryFast16x8c, VeryFast16x8d, VeryFast16x8e, VeryFast16x8f, VeryFast16x8g, }
-make_vf! { 16bit: u16x16, VeryFast16x16a, VeryFast16x16b, VeryFast16x16c, VeryFast16x16d, VeryFast16x16e, VeryFast16x16f, VeryFast16x16g, }
-make_vf! { 16bit: u16x32, VeryFast16x32a, VeryFast16x32b, VeryFast16x32c, VeryFast16x32d, VeryFast16x32e, VeryFast16x32f, VeryFast16x32g, }
-
-make_vf! { 8bit: u8x2, VeryFast8x2a, VeryFast8x2b, VeryFast8x2c, VeryFast8x2d, VeryFast8x2e, VeryFast8x2f, VeryFast8x2g, }
-make_vf! { 8bit: u8x4, VeryFast8x4a, VeryFast8x4b, VeryFast8x4c, VeryFast8x4d, VeryFast8x4e, VeryFast8x4f, VeryFast8x4g, }
-make_vf! { 8bit: u8x8, VeryFast8x8a, VeryFast8x8b, VeryFast8x8c, VeryFast8x8d, VeryFast8x8e, VeryFast8x8f, VeryFast8x8g, }
-make_vf! { 8bit: u8x16, VeryFast8x16a, VeryFast8x16b, VeryFast8x16c, VeryFast8x16d, VeryFast8x16e, VeryFast8x16f, VeryFast8x16g, }
-make_vf! { 8bit: u8x32, VeryFast8x32a, VeryFast8x32b, VeryFast8x32c, VeryFast8x32d, VeryFast8x32e, VeryFast8x32f, VeryFast8x32g, }
-make_vf! { 8bit: u8x64, VeryFast8x64a, VeryFast8x64b, VeryFast8x64c, VeryFast8x64d, VeryFast8x64e, VeryFast8x64f, VeryFast8x64g, }
+make_vf! { 64bit: u64x2, Seed384, VeryFast64x2a, VeryFast64x2b, VeryFast64x2c, VeryFast64x2d, VeryFast64x2e, VeryFast64x2f, VeryFast64x2g, }
+make_vf! { 64bit: u64x4, Seed768, VeryFast64x4a, VeryFast64x4b, VeryFast64x4c, VeryFast64x4d, VeryFast64x4e, VeryFast64x4f, VeryFast64x4g, }
+make_vf! { 64bit: u64x8, Seed1536, VeryFast64x8a, VeryFast64x8b, VeryFast64x8c, VeryFast64x8d, VeryFast64x8e, VeryFast64x8f, VeryFast64x8g, }
+
+make_vf! { 32bit: u32x2, [u8; 24], VeryFast32x2a, VeryFast32x2b, VeryFast32x2c, VeryFast32x2d, VeryFast32x2e, VeryFast32x2f, VeryFast32x2g, }
+make_vf! { 32bit: u32x4, Seed384, VeryFast32x4a, VeryFast32x4b, VeryFast32x4c, VeryFast32x4d, VeryFast32x4e, VeryFast32x4f, VeryFast32x4g, }
+make_vf! { 32bit: u32x8, Seed768, VeryFast32x8a, VeryFast32x8b, VeryFast32x8c, VeryFast32x8d, VeryFast32x8e, VeryFast32x8f, VeryFast32x8g, }
+make_vf! { 32bit: u32x16, Seed1536, VeryFast32x16a, VeryFast32x16b, VeryFast32x16c, VeryFast32x16d, VeryFast32x16e, VeryFast32x16f, VeryFast32x16g, }
+
+make_vf! { 16bit: u16x2, [u8; 12], VeryFast16x2a, VeryFast16x2b, VeryFast16x2c, VeryFast16x2d, VeryFast16x2e, VeryFast16x2f, VeryFast16x2g, }
+make_vf! { 16bit: u16x4, [u8; 24], VeryFast16x4a, VeryFast16x4b, VeryFast16x4c, VeryFast16x4d, VeryFast16x4e, VeryFast16x4f, VeryFast16x4g, }
+make_vf! { 16bit: u16x8, Seed384, VeryFast16x8a, VeryFast16x8b, VeryFast16x8c, VeryFast16x8d, VeryFast16x8e, VeryFast16x8f, VeryFast16x8g, }
+make_vf! { 16bit: u16x16, Seed768, VeryFast16x16a, VeryFast16x16b, VeryFast16x16c, VeryFast16x16d, VeryFast16x16e, VeryFast16x16f, VeryFast16x16g, }
+make_vf! { 16bit: u16x32, Seed1536, VeryFast16x32a, VeryFast16x32b, VeryFast16x32c, VeryFast16x32d, VeryFast16x32e, VeryFast16x32f, VeryFast16x32g, }
+
+make_vf! { 8bit: u8x2, [u8; 6], VeryFast8x2a, VeryFast8x2b, VeryFast8x2c, VeryFast8x2d, VeryFast8x2e, VeryFast8x2f, VeryFast8x2g, }
+make_vf! { 8bit: u8x4, [u8; 12], VeryFast8x4a, VeryFast8x4b, VeryFast8x4c, VeryFast8x4d, VeryFast8x4e, VeryFast8x4f, VeryFast8x4g, }
+make_vf! { 8bit: u8x8, [u8; 24], VeryFast8x8a, VeryFast8x8b, VeryFast8x8c, VeryFast8x8d, VeryFast8x8e, VeryFast8x8f, VeryFast8x8g, }
+make_vf! { 8bit: u8x16, Seed384, VeryFast8x16a, VeryFast8x16b, VeryFast8x16c, VeryFast8x16d, VeryFast8x16e, VeryFast8x16f, VeryFast8x16g, }
+make_vf! { 8bit: u8x32, Seed768, VeryFast8x32a, VeryFast8x32b, VeryFast8x32c, VeryFast8x32d, VeryFast8x32e, VeryFast8x32f, VeryFast8x32g, }
+make_vf! { 8bit: u8x64, Seed1536, VeryFast8x64a, VeryFast8x64b, VeryFast8x64c, VeryFast8x64d, VeryFast8x64e, VeryFast8x64f, VeryFast8x64g, }
@@ -0,0 +1,48 @@
+//! The widening multiply of each 64-bit lane's low 32 bits, as
+//! `_mm_mul_epu32`: the instruction itself on x86_64, masked 64-bit multiplies
+//! everywhere else.
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use rng_impl::*;
+
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+pub fn mul_low32(a: u64x2, b: u64x2) -> u64x2 {
+    u64x2::from_bits(unsafe { _mm_mul_epu32(__m128i::from_bits(a), __m128i::from_bits(b)) })
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub use self::portable as mul_low32;
+
+#[cfg(any(test, not(target_arch = "x86_64")))]
+#[inline(always)]
+pub fn portable(a: u64x2, b: u64x2) -> u64x2 {
+    (a & 0xffff_ffff) * (b & 0xffff_ffff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portable_matches() {
+        let edges = [0, 1, 0xffff_ffff, 0x1_0000_0000, u64::max_value()];
+        let mut rng = SplitMix64::new(0);
+        let samples = edges.iter().map(|&x| u64x2::new(x, !x));
+        let random = (0..64).map(|_| u64x2::new(rng.next_u64(), rng.next_u64()));
+
+        let samples: Vec<_> = samples.chain(random).collect();
+        for &a in &samples {
+            for &b in &samples {
+                let expected = u64x2::new(
+                    (a.extract(0) & 0xffff_ffff) * (b.extract(0) & 0xffff_ffff),
+                    (a.extract(1) & 0xffff_ffff) * (b.extract(1) & 0xffff_ffff),
+                );
+                assert_eq!(mul_low32(a, b), expected);
+                assert_eq!(portable(a, b), expected);
+            }
+        }
+    }
+}
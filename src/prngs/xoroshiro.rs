@@ -1,13 +1,37 @@
+// `jump` and `long_jump` evaluate the jump polynomial on whole vectors, so
+// all lanes move together. Cloning and jumping hands out substreams that
+// don't overlap the original for the jump distance per lane. `blocks_from_rng`
+// already spaces lanes one `jump` apart, so use `long_jump` with it.
+
 use rand::AsByteSliceMut as RandAsByteSliceMut;
 use rng_impl::*;
 
+/// Jump polynomials for 2^64 and 2^96 steps.
+///
+/// - Source: <http://xoshiro.di.unimi.it/xoroshiro128starstar.c>
+const JUMP: [u64; 2] = [0xdf900294d8f554a5, 0x170865df4b3201fc];
+const LONG_JUMP: [u64; 2] = [0xd2a98b26625eee7b, 0xdddf9b1090aa7ac1];
+
 macro_rules! make_xoroshiro {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             s0: $vector,
             s1: $vector,
         }
 
+        impl_debug! { $rng_name { s0, s1 } }
+
+        impl_serde! {
+            $rng_name { s0, s1 } where |rng| {
+                if (rng.s0 | rng.s1).eq($vector::splat(0)).any() {
+                    Err("an all-zero lane")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
         impl SimdRng for $rng_name {
             type Result = $vector;
 
@@ -36,6 +60,12 @@ macro_rules! make_xoroshiro {
 
         impl_rngcore! { $rng_name }
 
+        impl SplittableRng for $rng_name {
+            fn split(&mut self) -> Self {
+                reseed(self)
+            }
+        }
+
         impl $rng_name {
             pub fn blocks_from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
                 struct Xoroshiro128 {
@@ -47,8 +77,6 @@ macro_rules! make_xoroshiro {
                     // TODO: investigate carry-less multiplication implementation
                     //       per the paper http://vigna.di.unimi.it/ftp/papers/ScrambledLinear.pdf
                     fn jump(&mut self) {
-                        const JUMP: [u64; 2] = [0xdf900294d8f554a5, 0x170865df4b3201fc];
-
                         let mut s0 = 0;
                         let mut s1 = 0;
                         for jump in &JUMP {
@@ -96,31 +124,63 @@ macro_rules! make_xoroshiro {
 
                 Ok(Self { s0, s1 })
             }
+
+            /// Advances every lane by 2^64 steps.
+            pub fn jump(&mut self) {
+                self.jump_by(&JUMP);
+            }
+
+            /// Advances every lane by 2^96 steps.
+            pub fn long_jump(&mut self) {
+                self.jump_by(&LONG_JUMP);
+            }
+
+            fn jump_by(&mut self, poly: &[u64; 2]) {
+                let mut s0 = $vector::splat(0);
+                let mut s1 = $vector::splat(0);
+                for word in poly {
+                    for b in 0..64 {
+                        if (word & 1 << b) != 0 {
+                            s0 ^= self.s0;
+                            s1 ^= self.s1;
+                        }
+                        self.step();
+                    }
+                }
+                self.s0 = s0;
+                self.s1 = s1;
+            }
+
+            /// The linear engine, without the scrambler.
+            #[inline(always)]
+            fn step(&mut self) {
+                let s0 = self.s0;
+                let s1 = self.s1 ^ s0;
+                self.s0 = s0.rotate_left_opt(24) ^ s1 ^ (s1 << 16); // a, b
+                self.s1 = s1.rotate_left_opt(37); // c
+            }
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let mut s = read_seed!(seed, $vector; 2);
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seeds = [$vector::default(); 2];
-                while seeds
+                // an all-zero lane would only ever output zero
+                let zero = s
                     .iter()
-                    .fold($vector::splat(0), |mask, &s| mask | s)
-                    .eq($vector::splat(0))
-                    .any()
-                {
-                    rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
-                }
+                    .fold($vector::splat(0), |mask, &x| mask | x)
+                    .eq($vector::splat(0));
+                s[0] = zero.select(lane_fill!($vector, 0), s[0]);
 
-                Ok(Self {
-                    s0: seeds[0],
-                    s1: seeds[1],
-                })
+                Self {
+                    s0: s[0],
+                    s1: s[1],
+                }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -129,7 +189,68 @@ macro_rules! make_xoroshiro {
 // (multiple parameters could be used, though slow on older hardware)
 // (jumping is possible)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                             Probability
-make_xoroshiro! { Xoroshiro128StarStarX2, u64x2 } // ≈ 2^2 * l / 2^128 ≈ l * 2^-126
-make_xoroshiro! { Xoroshiro128StarStarX4, u64x4 } // ≈ 4^2 * l / 2^128 ≈ l * 2^-124
-make_xoroshiro! { Xoroshiro128StarStarX8, u64x8 } // ≈ 8^2 * l / 2^128 ≈ l * 2^-122
+// Listing probability of overlap somewhere:                                       Probability
+make_xoroshiro! { Xoroshiro128StarStarX2, u64x2, [u8; 32] } // ≈ 2^2 * l / 2^128 ≈ l * 2^-126
+make_xoroshiro! { Xoroshiro128StarStarX4, u64x4, Seed512  } // ≈ 4^2 * l / 2^128 ≈ l * 2^-124
+make_xoroshiro! { Xoroshiro128StarStarX8, u64x8, Seed1024 } // ≈ 8^2 * l / 2^128 ≈ l * 2^-122
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jump_matches_blocks() {
+        // `blocks_from_rng` jumps each lane one further than the last
+        let blocks = Xoroshiro128StarStarX4::blocks_from_rng(SplitMix64::new(0)).unwrap();
+        let mut jumped = blocks.clone();
+        jumped.jump();
+
+        for i in 0..3 {
+            assert_eq!(jumped.s0.extract(i), blocks.s0.extract(i + 1));
+            assert_eq!(jumped.s1.extract(i), blocks.s1.extract(i + 1));
+        }
+    }
+
+    /// The reference `jump`/`long_jump` from xoroshiro128starstar.c, on a
+    /// single lane.
+    fn reference_jump(mut s: [u64; 2], poly: &[u64; 2]) -> [u64; 2] {
+        let mut acc = [0; 2];
+        for &word in poly {
+            for b in 0..64 {
+                if word >> b & 1 != 0 {
+                    acc[0] ^= s[0];
+                    acc[1] ^= s[1];
+                }
+                let s1 = s[0] ^ s[1];
+                s[0] = s[0].rotate_left(24) ^ s1 ^ (s1 << 16);
+                s[1] = s1.rotate_left(37);
+            }
+        }
+        acc
+    }
+
+    macro_rules! test_reference_jump {
+        ($fnn:ident, $rng:ident, $vector:ident) => {
+            #[test]
+            fn $fnn() {
+                let start = $rng::seed_from_u64(0);
+                let mut jumped = start.clone();
+                jumped.jump();
+                let mut long_jumped = start.clone();
+                long_jumped.long_jump();
+
+                for i in 0..$vector::lanes() {
+                    let s = [start.s0.extract(i), start.s1.extract(i)];
+                    let j = reference_jump(s, &JUMP);
+                    assert_eq!([jumped.s0.extract(i), jumped.s1.extract(i)], j);
+                    let j = reference_jump(s, &LONG_JUMP);
+                    assert_eq!([long_jumped.s0.extract(i), long_jumped.s1.extract(i)], j);
+                }
+            }
+        };
+    }
+
+    test_reference_jump! { reference_jump_x2, Xoroshiro128StarStarX2, u64x2 }
+    test_reference_jump! { reference_jump_x4, Xoroshiro128StarStarX4, u64x4 }
+    test_reference_jump! { reference_jump_x8, Xoroshiro128StarStarX8, u64x8 }
+}
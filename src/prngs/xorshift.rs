@@ -1,11 +1,24 @@
 use rng_impl::*;
 
 macro_rules! make_xorshift {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             x: $vector,
         }
 
+        impl_debug! { $rng_name { x } }
+
+        impl_serde! {
+            $rng_name { x } where |rng| {
+                if rng.x.eq($vector::splat(0)).any() {
+                    Err("an all-zero lane")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
         impl_rngcore! { $rng_name }
 
         impl SimdRng for $rng_name {
@@ -21,22 +34,18 @@ macro_rules! make_xorshift {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
-
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seed = [$vector::default(); 1];
-                rng.try_fill_bytes(seed.as_byte_slice_mut())?;
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let x = read_seed!(seed, $vector; 1)[0];
 
-                while seed[0].eq($vector::splat(0)).any() {
-                    rng.try_fill_bytes(seed.as_byte_slice_mut())?;
-                }
+                // zero is a fixed point
+                let x = x.eq($vector::splat(0)).select(lane_fill!($vector, 0), x);
 
-                Ok(Self { x: seed[0] })
+                Self { x }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -45,14 +54,15 @@ macro_rules! make_xorshift {
 // (multiple parameters could be used, though slow on older hardware)
 // (jumping is possible)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                                 Probability
-make_xorshift! { Xorshift32x2,  u32x2  } // ≈ 2^2  * l / 2^32 ≈ l * 2^-30
-make_xorshift! { Xorshift32x4,  u32x4  } // ≈ 4^2  * l / 2^32 ≈ l * 2^-28
-make_xorshift! { Xorshift32x8,  u32x8  } // ≈ 8^2  * l / 2^32 ≈ l * 2^-26
-make_xorshift! { Xorshift32x16, u32x16 } // ≈ 16^2 * l / 2^32 ≈ l * 2^-24
+// Listing probability of overlap somewhere:                                           Probability
+make_xorshift! { Xorshift32x2,  u32x2,  [u8; 8]  } // ≈ 2^2  * l / 2^32 ≈ l * 2^-30
+make_xorshift! { Xorshift32x4,  u32x4,  [u8; 16] } // ≈ 4^2  * l / 2^32 ≈ l * 2^-28
+make_xorshift! { Xorshift32x8,  u32x8,  [u8; 32] } // ≈ 8^2  * l / 2^32 ≈ l * 2^-26
+make_xorshift! { Xorshift32x16, u32x16, Seed512  } // ≈ 16^2 * l / 2^32 ≈ l * 2^-24
 
 macro_rules! make_xorshift128 {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             x: $vector,
             y: $vector,
@@ -60,6 +70,18 @@ macro_rules! make_xorshift128 {
             w: $vector,
         }
 
+        impl_debug! { $rng_name { x, y, z, w } }
+
+        impl_serde! {
+            $rng_name { x, y, z, w } where |rng| {
+                if (rng.x | rng.y | rng.z | rng.w).eq($vector::splat(0)).any() {
+                    Err("an all-zero lane")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
         impl_rngcore! { $rng_name }
 
         impl SimdRng for $rng_name {
@@ -79,31 +101,27 @@ macro_rules! make_xorshift128 {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let mut s = read_seed!(seed, $vector; 4);
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seeds = [$vector::default(); 4];
-                // while any lane has all zero seeds
-                while seeds
+                // an all-zero lane would only ever output zero
+                let zero = s
                     .iter()
-                    .fold($vector::splat(0), |mask, &s| mask | s)
-                    .eq($vector::splat(0))
-                    .any()
-                {
-                    rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
+                    .fold($vector::splat(0), |mask, &x| mask | x)
+                    .eq($vector::splat(0));
+                s[0] = zero.select(lane_fill!($vector, 0), s[0]);
+
+                Self {
+                    x: s[0],
+                    y: s[1],
+                    z: s[2],
+                    w: s[3],
                 }
-
-                Ok(Self {
-                    x: seeds[0],
-                    y: seeds[1],
-                    z: seeds[2],
-                    w: seeds[3],
-                })
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -112,8 +130,8 @@ macro_rules! make_xorshift128 {
 // (multiple parameters could be used, though slow on older hardware)
 // (jumping is possible)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                         Probability
-make_xorshift128! { Xorshift128x2,  u32x2  } // ≈ 2^2  * l / 2^128 ≈ l * 2^-126
-make_xorshift128! { Xorshift128x4,  u32x4  } // ≈ 4^2  * l / 2^128 ≈ l * 2^-124
-make_xorshift128! { Xorshift128x8,  u32x8  } // ≈ 8^2  * l / 2^128 ≈ l * 2^-122
-make_xorshift128! { Xorshift128x16, u32x16 } // ≈ 16^2 * l / 2^128 ≈ l * 2^-120
+// Listing probability of overlap somewhere:                                   Probability
+make_xorshift128! { Xorshift128x2,  u32x2,  [u8; 32] } // ≈ 2^2  * l / 2^128 ≈ l * 2^-126
+make_xorshift128! { Xorshift128x4,  u32x4,  Seed512  } // ≈ 4^2  * l / 2^128 ≈ l * 2^-124
+make_xorshift128! { Xorshift128x8,  u32x8,  Seed1024 } // ≈ 8^2  * l / 2^128 ≈ l * 2^-122
+make_xorshift128! { Xorshift128x16, u32x16, Seed2048 } // ≈ 16^2 * l / 2^128 ≈ l * 2^-120
@@ -1,12 +1,25 @@
 use rng_impl::*;
 
 macro_rules! make_xorshift128plus {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             s0: $vector,
             s1: $vector,
         }
 
+        impl_debug! { $rng_name { s0, s1 } }
+
+        impl_serde! {
+            $rng_name { s0, s1 } where |rng| {
+                if (rng.s0 | rng.s1).eq($vector::splat(0)).any() {
+                    Err("an all-zero lane")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
         impl_rngcore! { $rng_name }
 
         impl SimdRng for $rng_name {
@@ -28,28 +41,25 @@ macro_rules! make_xorshift128plus {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let mut s = read_seed!(seed, $vector; 2);
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seeds = [$vector::default(); 2];
-                while seeds
+                // an all-zero lane would only ever output zero
+                let zero = s
                     .iter()
-                    .fold($vector::splat(0), |mask, &s| mask | s)
-                    .eq($vector::splat(0))
-                    .any()
-                {
-                    rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
-                }
+                    .fold($vector::splat(0), |mask, &x| mask | x)
+                    .eq($vector::splat(0));
+                s[0] = zero.select(lane_fill!($vector, 0), s[0]);
 
-                Ok(Self {
-                    s0: seeds[0],
-                    s1: seeds[1],
-                })
+                Self {
+                    s0: s[0],
+                    s1: s[1],
+                }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -58,7 +68,7 @@ macro_rules! make_xorshift128plus {
 // (multiple parameters could be used, though slow on older hardware)
 // (jumping is possible)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                              Probability
-make_xorshift128plus! { Xorshift128PlusX2, u64x2 } // ≈ 2^2 * l / 2^128 ≈ l * 2^-126
-make_xorshift128plus! { Xorshift128PlusX4, u64x4 } // ≈ 4^2 * l / 2^128 ≈ l * 2^-124
-make_xorshift128plus! { Xorshift128PlusX8, u64x8 } // ≈ 8^2 * l / 2^128 ≈ l * 2^-122
+// Listing probability of overlap somewhere:                                        Probability
+make_xorshift128plus! { Xorshift128PlusX2, u64x2, [u8; 32] } // ≈ 2^2 * l / 2^128 ≈ l * 2^-126
+make_xorshift128plus! { Xorshift128PlusX4, u64x4, Seed512  } // ≈ 4^2 * l / 2^128 ≈ l * 2^-124
+make_xorshift128plus! { Xorshift128PlusX8, u64x8, Seed1024 } // ≈ 8^2 * l / 2^128 ≈ l * 2^-122
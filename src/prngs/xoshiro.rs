@@ -1,6 +1,50 @@
+// `jump` and `long_jump` evaluate the jump polynomial on whole vectors, so
+// all lanes move together. Cloning and jumping hands out substreams that
+// don't overlap the original for the jump distance per lane. `blocks_from_rng`
+// already spaces lanes one `jump` apart, so use `long_jump` with it.
+
 use rand::AsByteSliceMut as RandAsByteSliceMut;
 use rng_impl::*;
 
+/// Jump polynomials: bit `i` is the coefficient of `x^i` in `x^(2^k)` modulo
+/// the characteristic polynomial, lowest word first.
+///
+/// - Source: <http://xoshiro.di.unimi.it/>
+const JUMP_256: [u64; 4] = [
+    0x180ec6d33cfd0aba,
+    0xd5a61266f0c9392c,
+    0xa9582618e03fc9aa,
+    0x39abdc4529b1661c,
+];
+const LONG_JUMP_256: [u64; 4] = [
+    0x76e15d3efefdcbbf,
+    0xc5004e441c522fb3,
+    0x77710069854ee241,
+    0x39109bb02acbe635,
+];
+const JUMP_128: [u32; 4] = [0x8764000b, 0xf542d2d3, 0x6fa035c3, 0x77f2db5b];
+const LONG_JUMP_128: [u32; 4] = [0xb523952e, 0x0b6f099f, 0xccf5a0ef, 0x1c580662];
+const JUMP_512: [u64; 8] = [
+    0x33ed89b6e7a353f9,
+    0x760083d7955323be,
+    0x2837f2fbb5f22fae,
+    0x4b8c5674d309511c,
+    0xb11ac47a7ba28c25,
+    0xf1be7667092bcc1c,
+    0x53851efdb6df0aaf,
+    0x1ebbc8b23eaf25db,
+];
+const LONG_JUMP_512: [u64; 8] = [
+    0x11467fef8f921d28,
+    0xa2a819f2e79c8ea8,
+    0xa8299fc284b3959a,
+    0xb4d347340ca63ee1,
+    0x1cb0940bedbff6ce,
+    0xd956c5c4fa1f8e17,
+    0x915e38fd4eda93bc,
+    0x5b3ccdfa5d7daca5,
+];
+
 /// Used from `blocks_from_rng`
 struct Xoshiro256 {
     s0: u64,
@@ -13,18 +57,11 @@ impl Xoshiro256 {
     // TODO: investigate carry-less multiplication implementation
     //       per the paper http://vigna.di.unimi.it/ftp/papers/ScrambledLinear.pdf
     fn jump(&mut self) {
-        const JUMP: [u64; 4] = [
-            0x180ec6d33cfd0aba,
-            0xd5a61266f0c9392c,
-            0xa9582618e03fc9aa,
-            0x39abdc4529b1661c,
-        ];
-
         let mut s0 = 0;
         let mut s1 = 0;
         let mut s2 = 0;
         let mut s3 = 0;
-        for jump in &JUMP {
+        for jump in &JUMP_256 {
             for b in 0..64 {
                 if (jump & 1 << b) != 0 {
                     s0 ^= self.s0;
@@ -53,7 +90,8 @@ impl Xoshiro256 {
 }
 
 macro_rules! make_xoshiro256 {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             s0: $vector,
             s1: $vector,
@@ -61,6 +99,18 @@ macro_rules! make_xoshiro256 {
             s3: $vector,
         }
 
+        impl_debug! { $rng_name { s0, s1, s2, s3 } }
+
+        impl_serde! {
+            $rng_name { s0, s1, s2, s3 } where |rng| {
+                if (rng.s0 | rng.s1 | rng.s2 | rng.s3).eq($vector::splat(0)).any() {
+                    Err("an all-zero lane")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
         impl $rng_name {
             pub fn blocks_from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
                 let mut seed = [0; 4];
@@ -94,10 +144,63 @@ macro_rules! make_xoshiro256 {
 
                 Ok(Self { s0, s1, s2, s3 })
             }
+
+            /// Advances every lane by 2^128 steps.
+            pub fn jump(&mut self) {
+                self.jump_by(&JUMP_256);
+            }
+
+            /// Advances every lane by 2^192 steps.
+            pub fn long_jump(&mut self) {
+                self.jump_by(&LONG_JUMP_256);
+            }
+
+            fn jump_by(&mut self, poly: &[u64; 4]) {
+                let mut s0 = $vector::splat(0);
+                let mut s1 = $vector::splat(0);
+                let mut s2 = $vector::splat(0);
+                let mut s3 = $vector::splat(0);
+                for word in poly {
+                    for b in 0..64 {
+                        if (word & 1 << b) != 0 {
+                            s0 ^= self.s0;
+                            s1 ^= self.s1;
+                            s2 ^= self.s2;
+                            s3 ^= self.s3;
+                        }
+                        self.step();
+                    }
+                }
+                self.s0 = s0;
+                self.s1 = s1;
+                self.s2 = s2;
+                self.s3 = s3;
+            }
+
+            /// The linear engine, without the scrambler.
+            #[inline(always)]
+            fn step(&mut self) {
+                let t = self.s1 << 17;
+
+                self.s2 ^= self.s0;
+                self.s3 ^= self.s1;
+                self.s1 ^= self.s2;
+                self.s0 ^= self.s3;
+
+                self.s2 ^= t;
+
+                self.s3 = self.s3.rotate_left_opt(45);
+            }
         }
 
         impl_rngcore! { $rng_name }
 
+        impl SplittableRng for $rng_name {
+            fn split(&mut self) -> Self {
+                reseed(self)
+            }
+        }
+
         impl SimdRng for $rng_name {
             type Result = $vector;
 
@@ -129,30 +232,27 @@ macro_rules! make_xoshiro256 {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let mut s = read_seed!(seed, $vector; 4);
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seeds = [$vector::default(); 4];
-                while seeds
+                // an all-zero lane would only ever output zero
+                let zero = s
                     .iter()
-                    .fold($vector::splat(0), |mask, &s| mask | s)
-                    .eq($vector::splat(0))
-                    .any()
-                {
-                    rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
+                    .fold($vector::splat(0), |mask, &x| mask | x)
+                    .eq($vector::splat(0));
+                s[0] = zero.select(lane_fill!($vector, 0), s[0]);
+
+                Self {
+                    s0: s[0],
+                    s1: s[1],
+                    s2: s[2],
+                    s3: s[3],
                 }
-
-                Ok(Self {
-                    s0: seeds[0],
-                    s1: seeds[1],
-                    s2: seeds[2],
-                    s3: seeds[3],
-                })
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -161,10 +261,10 @@ macro_rules! make_xoshiro256 {
 // (multiple parameters could be used, though slow on older hardware)
 // (jumping is possible)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                            Probability
-make_xoshiro256! { Xoshiro256StarStarX2, u64x2 } // ≈ 2^2 * l / 2^256 ≈ l * 2^-254
-make_xoshiro256! { Xoshiro256StarStarX4, u64x4 } // ≈ 4^2 * l / 2^256 ≈ l * 2^-252
-make_xoshiro256! { Xoshiro256StarStarX8, u64x8 } // ≈ 8^2 * l / 2^256 ≈ l * 2^-250
+// Listing probability of overlap somewhere:                                      Probability
+make_xoshiro256! { Xoshiro256StarStarX2, u64x2, Seed512  } // ≈ 2^2 * l / 2^256 ≈ l * 2^-254
+make_xoshiro256! { Xoshiro256StarStarX4, u64x4, Seed1024 } // ≈ 4^2 * l / 2^256 ≈ l * 2^-252
+make_xoshiro256! { Xoshiro256StarStarX8, u64x8, Seed2048 } // ≈ 8^2 * l / 2^256 ≈ l * 2^-250
 
 /// Used for `blocks_from_rng`
 struct Xoshiro128 {
@@ -178,13 +278,11 @@ impl Xoshiro128 {
     // TODO: investigate carry-less multiplication implementation
     //       per the paper http://vigna.di.unimi.it/ftp/papers/ScrambledLinear.pdf
     fn jump(&mut self) {
-        const JUMP: [u32; 4] = [0x8764000b, 0xf542d2d3, 0x6fa035c3, 0x77f2db5b];
-
         let mut s0 = 0;
         let mut s1 = 0;
         let mut s2 = 0;
         let mut s3 = 0;
-        for jump in &JUMP {
+        for jump in &JUMP_128 {
             for b in 0..32 {
                 if (jump & 1 << b) != 0 {
                     s0 ^= self.s0;
@@ -213,7 +311,8 @@ impl Xoshiro128 {
 }
 
 macro_rules! make_xoshiro128 {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             s0: $vector,
             s1: $vector,
@@ -221,6 +320,18 @@ macro_rules! make_xoshiro128 {
             s3: $vector,
         }
 
+        impl_debug! { $rng_name { s0, s1, s2, s3 } }
+
+        impl_serde! {
+            $rng_name { s0, s1, s2, s3 } where |rng| {
+                if (rng.s0 | rng.s1 | rng.s2 | rng.s3).eq($vector::splat(0)).any() {
+                    Err("an all-zero lane")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
         impl $rng_name {
             pub fn blocks_from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
                 let mut seed = [0; 4];
@@ -253,10 +364,63 @@ macro_rules! make_xoshiro128 {
 
                 Ok(Self { s0, s1, s2, s3 })
             }
+
+            /// Advances every lane by 2^64 steps.
+            pub fn jump(&mut self) {
+                self.jump_by(&JUMP_128);
+            }
+
+            /// Advances every lane by 2^96 steps.
+            pub fn long_jump(&mut self) {
+                self.jump_by(&LONG_JUMP_128);
+            }
+
+            fn jump_by(&mut self, poly: &[u32; 4]) {
+                let mut s0 = $vector::splat(0);
+                let mut s1 = $vector::splat(0);
+                let mut s2 = $vector::splat(0);
+                let mut s3 = $vector::splat(0);
+                for word in poly {
+                    for b in 0..32 {
+                        if (word & 1 << b) != 0 {
+                            s0 ^= self.s0;
+                            s1 ^= self.s1;
+                            s2 ^= self.s2;
+                            s3 ^= self.s3;
+                        }
+                        self.step();
+                    }
+                }
+                self.s0 = s0;
+                self.s1 = s1;
+                self.s2 = s2;
+                self.s3 = s3;
+            }
+
+            /// The linear engine, without the scrambler.
+            #[inline(always)]
+            fn step(&mut self) {
+                let t = self.s1 << 9;
+
+                self.s2 ^= self.s0;
+                self.s3 ^= self.s1;
+                self.s1 ^= self.s2;
+                self.s0 ^= self.s3;
+
+                self.s2 ^= t;
+
+                self.s3 = self.s3.rotate_left_opt(11);
+            }
         }
 
         impl_rngcore! { $rng_name }
 
+        impl SplittableRng for $rng_name {
+            fn split(&mut self) -> Self {
+                reseed(self)
+            }
+        }
+
         impl SimdRng for $rng_name {
             type Result = $vector;
 
@@ -282,47 +446,109 @@ macro_rules! make_xoshiro128 {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let mut s = read_seed!(seed, $vector; 4);
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seeds = [$vector::default(); 4];
-                while seeds
+                // an all-zero lane would only ever output zero
+                let zero = s
                     .iter()
-                    .fold($vector::splat(0), |mask, &s| mask | s)
-                    .eq($vector::splat(0))
-                    .any()
-                {
-                    rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
+                    .fold($vector::splat(0), |mask, &x| mask | x)
+                    .eq($vector::splat(0));
+                s[0] = zero.select(lane_fill!($vector, 0), s[0]);
+
+                Self {
+                    s0: s[0],
+                    s1: s[1],
+                    s2: s[2],
+                    s3: s[3],
                 }
-
-                Ok(Self {
-                    s0: seeds[0],
-                    s1: seeds[1],
-                    s2: seeds[2],
-                    s3: seeds[3],
-                })
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
 
-make_xoshiro128! { Xoshiro128StarStarX2,  u32x2  } // ≈ 2^2 * l / 2^128 ≈ l * 2^-126
-make_xoshiro128! { Xoshiro128StarStarX4,  u32x4  } // ≈ 4^2 * l / 2^128 ≈ l * 2^-124
-make_xoshiro128! { Xoshiro128StarStarX8,  u32x8  } // ≈ 8^2 * l / 2^128 ≈ l * 2^-122
-make_xoshiro128! { Xoshiro128StarStarX16, u32x16 } // ≈ 8^2 * l / 2^128 ≈ l * 2^-120
+make_xoshiro128! { Xoshiro128StarStarX2,  u32x2,  [u8; 32] } // ≈ 2^2 * l / 2^128 ≈ l * 2^-126
+make_xoshiro128! { Xoshiro128StarStarX4,  u32x4,  Seed512  } // ≈ 4^2 * l / 2^128 ≈ l * 2^-124
+make_xoshiro128! { Xoshiro128StarStarX8,  u32x8,  Seed1024 } // ≈ 8^2 * l / 2^128 ≈ l * 2^-122
+make_xoshiro128! { Xoshiro128StarStarX16, u32x16, Seed2048 } // ≈ 8^2 * l / 2^128 ≈ l * 2^-120
 
 macro_rules! make_xoshiro512 {
-    ($rng_name:ident, $vector:ident) => {
+    ($rng_name:ident, $vector:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             s: [$vector; 8],
         }
 
+        impl_debug! { $rng_name { s } }
+
+        impl_serde! {
+            $rng_name { s } where |rng| {
+                if rng.s.iter().fold($vector::splat(0), |mask, &x| mask | x).eq($vector::splat(0)).any() {
+                    Err("an all-zero lane")
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
+        impl $rng_name {
+            /// Advances every lane by 2^256 steps.
+            pub fn jump(&mut self) {
+                self.jump_by(&JUMP_512);
+            }
+
+            /// Advances every lane by 2^384 steps.
+            pub fn long_jump(&mut self) {
+                self.jump_by(&LONG_JUMP_512);
+            }
+
+            fn jump_by(&mut self, poly: &[u64; 8]) {
+                let mut s = [$vector::splat(0); 8];
+                for word in poly {
+                    for b in 0..64 {
+                        if (word & 1 << b) != 0 {
+                            for (s, x) in s.iter_mut().zip(&self.s) {
+                                *s ^= *x;
+                            }
+                        }
+                        self.step();
+                    }
+                }
+                self.s = s;
+            }
+
+            /// The linear engine, without the scrambler.
+            #[inline(always)]
+            fn step(&mut self) {
+                let t = self.s[1] << 11;
+
+                self.s[2] ^= self.s[0];
+                self.s[5] ^= self.s[1];
+                self.s[1] ^= self.s[2];
+                self.s[7] ^= self.s[3];
+                self.s[3] ^= self.s[4];
+                self.s[4] ^= self.s[5];
+                self.s[0] ^= self.s[6];
+                self.s[6] ^= self.s[7];
+
+                self.s[6] ^= t;
+
+                self.s[7] = self.s[7].rotate_left_opt(21);
+            }
+        }
+
         impl_rngcore! { $rng_name }
 
+        impl SplittableRng for $rng_name {
+            fn split(&mut self) -> Self {
+                reseed(self)
+            }
+        }
+
         impl SimdRng for $rng_name {
             type Result = $vector;
 
@@ -350,25 +576,22 @@ macro_rules! make_xoshiro512 {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
+            type Seed = $seed;
 
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let mut s = read_seed!(seed, $vector; 8);
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seeds = [$vector::default(); 8];
-                while seeds
+                // an all-zero lane would only ever output zero
+                let zero = s
                     .iter()
-                    .fold($vector::splat(0), |mask, &s| mask | s)
-                    .eq($vector::splat(0))
-                    .any()
-                {
-                    rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
-                }
+                    .fold($vector::splat(0), |mask, &x| mask | x)
+                    .eq($vector::splat(0));
+                s[0] = zero.select(lane_fill!($vector, 0), s[0]);
 
-                Ok(Self { s: seeds })
+                Self { s }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -377,7 +600,185 @@ macro_rules! make_xoshiro512 {
 // (multiple parameters could be used, though slow on older hardware)
 // (jumping is possible)
 #[rustfmt::skip]
-// Listing probability of overlap somewhere:                            Probability
-make_xoshiro512! { Xoshiro512StarStarX2, u64x2 } // ≈ 2^2 * l / 2^512 ≈ l * 2^-510
-make_xoshiro512! { Xoshiro512StarStarX4, u64x4 } // ≈ 4^2 * l / 2^512 ≈ l * 2^-508
-make_xoshiro512! { Xoshiro512StarStarX8, u64x8 } // ≈ 8^2 * l / 2^512 ≈ l * 2^-506
+// Listing probability of overlap somewhere:                                      Probability
+make_xoshiro512! { Xoshiro512StarStarX2, u64x2, Seed1024 } // ≈ 2^2 * l / 2^512 ≈ l * 2^-510
+make_xoshiro512! { Xoshiro512StarStarX4, u64x4, Seed2048 } // ≈ 4^2 * l / 2^512 ≈ l * 2^-508
+make_xoshiro512! { Xoshiro512StarStarX8, u64x8, Seed4096 } // ≈ 8^2 * l / 2^512 ≈ l * 2^-506
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_jump {
+        ($fnn:ident, $rng:ident) => {
+            #[test]
+            fn $fnn() {
+                // `blocks_from_rng` jumps each lane one further than the last
+                let blocks = $rng::blocks_from_rng(SplitMix64::new(0)).unwrap();
+                let mut jumped = blocks.clone();
+                jumped.jump();
+
+                for i in 0..blocks.s0.lanes() - 1 {
+                    assert_eq!(jumped.s0.extract(i), blocks.s0.extract(i + 1));
+                    assert_eq!(jumped.s1.extract(i), blocks.s1.extract(i + 1));
+                    assert_eq!(jumped.s2.extract(i), blocks.s2.extract(i + 1));
+                    assert_eq!(jumped.s3.extract(i), blocks.s3.extract(i + 1));
+                }
+            }
+        };
+    }
+
+    test_jump! { jump_xoshiro256_x4, Xoshiro256StarStarX4 }
+    test_jump! { jump_xoshiro128_x8, Xoshiro128StarStarX8 }
+
+    // Scalar xoshiro steps, one lane of state per slice
+    fn step256(s: &mut [u64]) {
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+    }
+
+    fn step128(s: &mut [u32]) {
+        let t = s[1] << 9;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(11);
+    }
+
+    fn step512(s: &mut [u64]) {
+        let t = s[1] << 11;
+        s[2] ^= s[0];
+        s[5] ^= s[1];
+        s[1] ^= s[2];
+        s[7] ^= s[3];
+        s[3] ^= s[4];
+        s[4] ^= s[5];
+        s[0] ^= s[6];
+        s[6] ^= s[7];
+        s[6] ^= t;
+        s[7] = s[7].rotate_left(21);
+    }
+
+    /// The reference `jump`/`long_jump` from xoshiro*.c, on a single lane.
+    fn reference_jump<T>(s: &mut [T], poly: &[T], step: fn(&mut [T]))
+    where
+        T: Copy + Default + ::std::ops::BitXorAssign + Into<u64>,
+    {
+        let mut acc = vec![T::default(); s.len()];
+        for &word in poly {
+            for b in 0..8 * ::std::mem::size_of::<T>() {
+                if Into::<u64>::into(word) >> b & 1 != 0 {
+                    for (a, &x) in acc.iter_mut().zip(s.iter()) {
+                        *a ^= x;
+                    }
+                }
+                step(s);
+            }
+        }
+        s.copy_from_slice(&acc);
+    }
+
+    macro_rules! test_reference_jump {
+        (
+            $fnn:ident, $rng:ident, $vector:ident, $step:ident, $jump:ident, $long_jump:ident,
+            |$r:ident, $i:ident| $lane:expr
+        ) => {
+            #[test]
+            fn $fnn() {
+                let lane = |$r: &$rng, $i: usize| $lane;
+                let start = $rng::seed_from_u64(0);
+
+                let mut jumped = start.clone();
+                jumped.jump();
+                let mut long_jumped = start.clone();
+                long_jumped.long_jump();
+
+                for i in 0..$vector::lanes() {
+                    let mut s = lane(&start, i);
+                    reference_jump(&mut s, &$jump, $step);
+                    assert_eq!(lane(&jumped, i), s);
+
+                    let mut s = lane(&start, i);
+                    reference_jump(&mut s, &$long_jump, $step);
+                    assert_eq!(lane(&long_jumped, i), s);
+                }
+            }
+        };
+    }
+
+    macro_rules! xoshiro_lane {
+        ($r:ident, $i:ident) => {
+            vec![$r.s0.extract($i), $r.s1.extract($i), $r.s2.extract($i), $r.s3.extract($i)]
+        };
+    }
+
+    test_reference_jump! {
+        reference_jump_xoshiro256_x2, Xoshiro256StarStarX2, u64x2, step256, JUMP_256, LONG_JUMP_256,
+        |r, i| xoshiro_lane!(r, i)
+    }
+    test_reference_jump! {
+        reference_jump_xoshiro256_x4, Xoshiro256StarStarX4, u64x4, step256, JUMP_256, LONG_JUMP_256,
+        |r, i| xoshiro_lane!(r, i)
+    }
+    test_reference_jump! {
+        reference_jump_xoshiro256_x8, Xoshiro256StarStarX8, u64x8, step256, JUMP_256, LONG_JUMP_256,
+        |r, i| xoshiro_lane!(r, i)
+    }
+    test_reference_jump! {
+        reference_jump_xoshiro128_x2, Xoshiro128StarStarX2, u32x2, step128, JUMP_128, LONG_JUMP_128,
+        |r, i| xoshiro_lane!(r, i)
+    }
+    test_reference_jump! {
+        reference_jump_xoshiro128_x4, Xoshiro128StarStarX4, u32x4, step128, JUMP_128, LONG_JUMP_128,
+        |r, i| xoshiro_lane!(r, i)
+    }
+    test_reference_jump! {
+        reference_jump_xoshiro128_x8, Xoshiro128StarStarX8, u32x8, step128, JUMP_128, LONG_JUMP_128,
+        |r, i| xoshiro_lane!(r, i)
+    }
+    test_reference_jump! {
+        reference_jump_xoshiro128_x16, Xoshiro128StarStarX16, u32x16, step128, JUMP_128,
+        LONG_JUMP_128,
+        |r, i| xoshiro_lane!(r, i)
+    }
+    test_reference_jump! {
+        reference_jump_xoshiro512_x2, Xoshiro512StarStarX2, u64x2, step512, JUMP_512, LONG_JUMP_512,
+        |r, i| r.s.iter().map(|s| s.extract(i)).collect::<Vec<_>>()
+    }
+    test_reference_jump! {
+        reference_jump_xoshiro512_x4, Xoshiro512StarStarX4, u64x4, step512, JUMP_512, LONG_JUMP_512,
+        |r, i| r.s.iter().map(|s| s.extract(i)).collect::<Vec<_>>()
+    }
+    test_reference_jump! {
+        reference_jump_xoshiro512_x8, Xoshiro512StarStarX8, u64x8, step512, JUMP_512, LONG_JUMP_512,
+        |r, i| r.s.iter().map(|s| s.extract(i)).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn zero_seed_lanes_differ() {
+        let rng = Xoshiro256StarStarX4::from_seed(Default::default());
+        for i in 1..4 {
+            assert_ne!(rng.s0.extract(i), 0);
+            assert_ne!(rng.s0.extract(i), rng.s0.extract(0));
+        }
+    }
+
+    #[test]
+    fn split_is_disjoint_at_depth() {
+        let mut parent = Xoshiro256StarStarX4::seed_from_u64(0);
+        let mut child = parent.split();
+        let sibling = parent.split();
+        let grandchild = child.split();
+
+        assert_eq!(parent.clone().split(), parent.clone().split());
+        assert_ne!(grandchild, sibling);
+        assert_ne!(grandchild, parent.split());
+    }
+}
@@ -1,89 +1,115 @@
+use rand::AsByteSliceMut as RandAsByteSliceMut;
 use rng_impl::*;
 
-/// Used for `blocks_from_rng`
-#[allow(dead_code)]
+/// The 64-bit LCG underneath `Xsm32x*`, one lane at a time.
+///
+/// Used for `blocks_from_rng` and `seek`
 struct Xsm32 {
-    lcg_low: u32,
-    lcg_high: u32,
-    lcg_adder: u32,
+    lcg: u64,
+    lcg_adder: u64,
 }
 
 impl Xsm32 {
-    #[allow(clippy::cast_lossless, dead_code)]
-    fn seek_forward(&mut self) {
-        fn fast_forward_lcg64(mut val: u64, mut mul: u64, mut add: u64) -> u64 {
-            let mut how_far: u32 = 0xffff_ffff; // 2^32 - 1
-
-            loop {
-                if how_far & 1 != 0 {
-                    val = val.wrapping_mul(mul).wrapping_add(add);
-                }
-                how_far >>= 1;
-                if how_far == 0 {
-                    break;
-                }
-                add = add.wrapping_mul(mul).wrapping_add(add);
-                mul = mul.wrapping_mul(mul);
+    #[allow(clippy::cast_lossless)]
+    fn from_lanes(lcg_low: u32, lcg_high: u32, lcg_adder_low: u32, lcg_adder_high: u32) -> Self {
+        Self {
+            lcg: lcg_low as u64 | ((lcg_high as u64) << 32),
+            lcg_adder: lcg_adder_low as u64 | ((lcg_adder_high as u64) << 32),
+        }
+    }
+
+    /// Steps the LCG `how_far` times, in O(log how_far).
+    ///
+    /// `step_forwards` is `lcg = lcg * (2^32 + 1) + lcg_adder`, split into
+    /// 32-bit halves.
+    fn seek_forward(&mut self, mut how_far: u64) {
+        let mut mul: u64 = 0x0000_0001_0000_0001;
+        let mut add = self.lcg_adder;
+
+        while how_far != 0 {
+            if how_far & 1 != 0 {
+                self.lcg = self.lcg.wrapping_mul(mul).wrapping_add(add);
             }
-            val
+            how_far >>= 1;
+            add = add.wrapping_mul(mul).wrapping_add(add);
+            mul = mul.wrapping_mul(mul);
         }
+    }
 
-        // how_far -= 1; 2^x - 1
-        let mut lcg_state = self.lcg_low as u64 | ((self.lcg_high as u64) << 32);
-        let mul = 0x0000_0001_0000_0001;
-        lcg_state = fast_forward_lcg64(lcg_state, mul, self.lcg_adder as u64);
-        self.lcg_low = lcg_state as u32;
-        self.lcg_high = (lcg_state >> 32) as u32;
-
-        // xsm.generate();
-        let mut old_lcg_low = self.lcg_low;
-        self.lcg_low = self.lcg_low.wrapping_add(self.lcg_adder);
-        old_lcg_low = old_lcg_low.wrapping_add((self.lcg_low < self.lcg_adder) as u32);
-        self.lcg_high = self.lcg_high.wrapping_add(old_lcg_low);
+    fn lcg_low(&self) -> u32 {
+        self.lcg as u32
+    }
+
+    fn lcg_high(&self) -> u32 {
+        (self.lcg >> 32) as u32
     }
 }
 
 macro_rules! make_xsm32 {
-    ($rng_name:ident, $vec:ident) => {
+    ($rng_name:ident, $vec:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
+        pub struct $rng_name {
+            lcg_low: $vec,
+            lcg_high: $vec,
+            lcg_adder_low: $vec,
+            lcg_adder_high: $vec,
+        }
+
+        impl_debug! { $rng_name { lcg_low, lcg_high, lcg_adder_low, lcg_adder_high } }
+
+        impl_serde! { $rng_name { lcg_low, lcg_high, lcg_adder_low, lcg_adder_high } }
+
         impl $rng_name {
-            /*/// There's little documentation so I'm unclear how best to
-            /// implement this.
+            /// Lanes share one adder and are spaced evenly around the 2^64
+            /// period, so no two overlap for 2^64 / lanes outputs.
             pub fn blocks_from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seeds = [0; 3];
-                rng.try_fill_bytes(&mut seeds)?;
+                let mut seeds = [0_u32; 3];
+                rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
 
-                let mut scalar = Xsm32 {
-                    lcg_high: seeds[0] | 1,
-                    lcg_adder: seeds[1],
-                    lcg_low: seeds[2],
-                };
+                // as in `from_seed`
+                let lcg_adder_low = seeds[0] | 1;
+                let lcg_adder_high = seeds[1];
+                let lcg_high = lcg_adder_high.wrapping_add(seeds[2] << 16);
+                let lcg_low = lcg_adder_low;
+
+                let mut scalar = Xsm32::from_lanes(lcg_low, lcg_high, lcg_adder_low, lcg_adder_high);
+                let spacing = (1_u64 << 63) / ($vec::lanes() as u64 / 2);
 
-                let mut lcg_low = $vec::splat(scalar.lcg_low);
-                let mut lcg_high = $vec::splat(scalar.lcg_high);
-                let mut lcg_adder = $vec::splat(scalar.lcg_adder);
+                let mut lcg_low = $vec::splat(lcg_low);
+                let mut lcg_high = $vec::splat(lcg_high);
 
                 for i in 1..$vec::lanes() {
-                    // Each stream has 2^32 values before it begins to repeat
-                    // the next stream (except the last stream). For more
-                    // space in-between streams, use more jumps per stream.
-                    scalar.seek_forward();
-                    lcg_low = lcg_low.replace(i, scalar.lcg_low);
-                    lcg_high = lcg_high.replace(i, scalar.lcg_high);
-                    lcg_adder = lcg_adder.replace(i, scalar.lcg_adder);
+                    scalar.seek_forward(spacing);
+                    lcg_low = lcg_low.replace(i, scalar.lcg_low());
+                    lcg_high = lcg_high.replace(i, scalar.lcg_high());
                 }
 
-                Ok(Self { lcg_low, lcg_high, lcg_adder, history: $vec::splat(0) })
-            }*/
-        }
+                Ok(Self {
+                    lcg_low,
+                    lcg_high,
+                    lcg_adder_low: $vec::splat(lcg_adder_low),
+                    lcg_adder_high: $vec::splat(lcg_adder_high),
+                })
+            }
 
-        pub struct $rng_name {
-            lcg_low: $vec,
-            lcg_high: $vec,
-            lcg_adder_low: $vec,
-            lcg_adder_high: $vec,
-        }
+            /// Moves every lane `offset` outputs forward, in O(log offset).
+            ///
+            /// The period is 2^64, so `seek(offset.wrapping_neg())` moves
+            /// backward.
+            pub fn seek(&mut self, offset: u64) {
+                for i in 0..$vec::lanes() {
+                    let mut scalar = Xsm32::from_lanes(
+                        self.lcg_low.extract(i),
+                        self.lcg_high.extract(i),
+                        self.lcg_adder_low.extract(i),
+                        self.lcg_adder_high.extract(i),
+                    );
+                    scalar.seek_forward(offset);
+                    self.lcg_low = self.lcg_low.replace(i, scalar.lcg_low());
+                    self.lcg_high = self.lcg_high.replace(i, scalar.lcg_high());
+                }
+            }
 
-        impl $rng_name {
             #[inline]
             fn step_forwards(&mut self) {
                 let tmp = self.lcg_low + self.lcg_adder_high;
@@ -113,28 +139,25 @@ macro_rules! make_xsm32 {
         }
 
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
-
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            type Seed = $seed;
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seeds = [$vec::default(); 3];
-                rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seeds = read_seed!(seed, $vec; 3);
 
                 let lcg_adder_low = seeds[0] | 1;
                 let lcg_adder_high = seeds[1];
                 let lcg_high = lcg_adder_high + (seeds[2] << 16);
                 let lcg_low = lcg_adder_low;
 
-                Ok(Self {
+                Self {
                     lcg_adder_low,
                     lcg_adder_high,
                     lcg_high,
                     lcg_low,
-                })
+                }
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -142,71 +165,59 @@ macro_rules! make_xsm32 {
 // (where `l` is stream length)
 // (multiple parameters *might* be possible)
 // (jumping is possible)
-// Listing probability of overlap somewhere:            Probability
-make_xsm32! { Xsm32x2,  u32x2  } // ≈ 2^2  * l / 2^64 ≈ l * 2^-62
-make_xsm32! { Xsm32x4,  u32x4  } // ≈ 4^2  * l / 2^64 ≈ l * 2^-60
-make_xsm32! { Xsm32x8,  u32x8  } // ≈ 8^2  * l / 2^64 ≈ l * 2^-58
-make_xsm32! { Xsm32x16, u32x16 } // ≈ 16^2 * l / 2^64 ≈ l * 2^-56
-
-macro_rules! make_xsm64 {
-    ($rng_name:ident, $vec:ident, $vec64:ident) => {
-        impl $rng_name {
-            /*pub fn blocks_from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                fn fast_forward_lcg128(
-                    how_far_low: u64,
-                    how_far_high: u64,
-                    value_low: &mut u64,
-                    value_high: &mut u64,
-                    mul_low: u64,
-                    mul_high: u64,
-                    add_low: u64,
-                    add_high: u64,
-                ) {
-                    Uint32 value[4], mul[4], add[4], tmp[4];
-                    convert128_64to32(value_low, value_high, value);
-                    convert128_64to32(mul_low, mul_high, mul);
-                    convert128_64to32(add_low, add_high, add);
-                    while (1) {
-                        if (how_far_low & 1) {
-                            multiply_128(value, mul, value);
-                            add_128(value, add, value);
-                            //val = val * mul + add;
-                        }
-                        how_far_low = (how_far_low >> 1) | (how_far_high << 63);
-                        how_far_high >>= 1;
-                        if (how_far_low == 0 && how_far_high == 0) break;
-                        multiply_128(add, mul, tmp);
-                        add_128(add, tmp, add);
-                        //add = add * mul + add;
-                        multiply_128(mul, mul, mul);
-                        //mul = mul * mul;
-                    }
-                    value_low  = value[0] | (Uint64(value[1]) << 32);
-                    value_high = value[2] | (Uint64(value[3]) << 32);
-                    return;
-                }
+// Listing probability of overlap somewhere:                      Probability
+make_xsm32! { Xsm32x2,  u32x2,  [u8; 24] } // ≈ 2^2  * l / 2^64 ≈ l * 2^-62
+make_xsm32! { Xsm32x4,  u32x4,  Seed384  } // ≈ 4^2  * l / 2^64 ≈ l * 2^-60
+make_xsm32! { Xsm32x8,  u32x8,  Seed768  } // ≈ 8^2  * l / 2^64 ≈ l * 2^-58
+make_xsm32! { Xsm32x16, u32x16, Seed1536 } // ≈ 16^2 * l / 2^64 ≈ l * 2^-56
+
+/// The 128-bit LCG underneath `Xsm64x*`, one lane at a time.
+///
+/// Used for `blocks_from_rng` and `seek`
+struct Xsm64 {
+    lcg: u128,
+    lcg_adder: u128,
+}
 
-                let mut seeds = [0u32; 3];
-                rng.try_fill_bytes(&mut seeds)?;
+impl Xsm64 {
+    #[allow(clippy::cast_lossless)]
+    fn from_lanes(lcg_low: u64, lcg_high: u64, lcg_adder_low: u64, lcg_adder_high: u64) -> Self {
+        Self {
+            lcg: lcg_low as u128 | ((lcg_high as u128) << 64),
+            lcg_adder: lcg_adder_low as u128 | ((lcg_adder_high as u128) << 64),
+        }
+    }
 
-                let mut xsm = Self {
-                    lcg_high: seeds[0] | 1,
-                    lcg_adder: seeds[1],
-                    lcg_low: seeds[2],
-                    history: 0,
-                };
+    /// Steps the LCG `how_far` times, in O(log how_far).
+    ///
+    /// `step_forwards` is `lcg = lcg * (2^64 + 1) + lcg_adder`, split into
+    /// 64-bit halves.
+    fn seek_forward(&mut self, mut how_far: u128) {
+        let mut mul: u128 = (1 << 64) | 1;
+        let mut add = self.lcg_adder;
+
+        while how_far != 0 {
+            if how_far & 1 != 0 {
+                self.lcg = self.lcg.wrapping_mul(mul).wrapping_add(add);
+            }
+            how_far >>= 1;
+            add = add.wrapping_mul(mul).wrapping_add(add);
+            mul = mul.wrapping_mul(mul);
+        }
+    }
 
-                // if (!how_far_low && !how_far_high) return;
-                // if (!how_far_low--) how_far_high--;
-                fast_forward_lcg128(/*how_far_low, how_far_high,*/
-                    xsm.lcg_low, xsm.lcg_high, 1, 1, xsm.lcg_adder, 0
-                );
-                xsm.generate();
+    fn lcg_low(&self) -> u64 {
+        self.lcg as u64
+    }
 
-                Ok(xsm)
-            }*/
-        }
+    fn lcg_high(&self) -> u64 {
+        (self.lcg >> 64) as u64
+    }
+}
 
+macro_rules! make_xsm64 {
+    ($rng_name:ident, $vec:ident, $vec64:ident, $seed:ty) => {
+        #[derive(Clone, PartialEq, Eq)]
         pub struct $rng_name {
             lcg_low: $vec,
             lcg_high: $vec,
@@ -214,7 +225,67 @@ macro_rules! make_xsm64 {
             lcg_adder_high: $vec,
         }
 
+        impl_debug! { $rng_name { lcg_low, lcg_high, lcg_adder_low, lcg_adder_high } }
+
+        impl_serde! { $rng_name { lcg_low, lcg_high, lcg_adder_low, lcg_adder_high } }
+
         impl $rng_name {
+            /// Lanes share one adder and are spaced evenly around the 2^128
+            /// period, so no two overlap for 2^128 / lanes outputs.
+            pub fn blocks_from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
+                let mut seeds = [0_u64; 3];
+                rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
+
+                // the state `from_seed` gives a lane with these seed words:
+                // `seek_forward(1)` is its `step_forwards`, and the offset
+                // its `seeds[2] << 31` added to the high word
+                let seed_low = seeds[0];
+                let seed_high = seeds[1];
+                let lcg_adder_low = (seed_low << 1) | 1;
+                let lcg_adder_high = (seed_low >> 63) | (seed_high << 1);
+                let lcg_low = lcg_adder_low;
+                let lcg_high = lcg_adder_high ^ ((seed_high >> 63) << 63);
+
+                let mut scalar = Xsm64::from_lanes(lcg_low, lcg_high, lcg_adder_low, lcg_adder_high);
+                scalar.seek_forward(1);
+                scalar.lcg = scalar.lcg.wrapping_add((seeds[2] as u128) << 95);
+                let spacing = (1_u128 << 127) / ($vec::lanes() as u128 / 2);
+
+                let mut lcg_low = $vec::splat(scalar.lcg_low());
+                let mut lcg_high = $vec::splat(scalar.lcg_high());
+
+                for i in 1..$vec::lanes() {
+                    scalar.seek_forward(spacing);
+                    lcg_low = lcg_low.replace(i, scalar.lcg_low());
+                    lcg_high = lcg_high.replace(i, scalar.lcg_high());
+                }
+
+                Ok(Self {
+                    lcg_low,
+                    lcg_high,
+                    lcg_adder_low: $vec::splat(lcg_adder_low),
+                    lcg_adder_high: $vec::splat(lcg_adder_high),
+                })
+            }
+
+            /// Moves every lane `offset` outputs forward, in O(log offset).
+            ///
+            /// The period is 2^128, so `seek(offset.wrapping_neg())` moves
+            /// backward.
+            pub fn seek(&mut self, offset: u128) {
+                for i in 0..$vec::lanes() {
+                    let mut scalar = Xsm64::from_lanes(
+                        self.lcg_low.extract(i),
+                        self.lcg_high.extract(i),
+                        self.lcg_adder_low.extract(i),
+                        self.lcg_adder_high.extract(i),
+                    );
+                    scalar.seek_forward(offset);
+                    self.lcg_low = self.lcg_low.replace(i, scalar.lcg_low());
+                    self.lcg_high = self.lcg_high.replace(i, scalar.lcg_high());
+                }
+            }
+
             fn step_forwards(&mut self) {
                 let tmp = self.lcg_low + self.lcg_adder_high;
                 self.lcg_low += self.lcg_adder_low;
@@ -247,18 +318,13 @@ macro_rules! make_xsm64 {
 
         #[rustfmt::skip]
         impl SeedableRng for $rng_name {
-            type Seed = [u8; 0];
-
-            fn from_seed(_seed: Self::Seed) -> Self {
-                unimplemented!("`SeedableRng::from_seed` is unimplemented for some PRNG families")
-            }
+            type Seed = $seed;
 
-            fn from_rng<R: Rng>(mut rng: R) -> Result<Self, Error> {
-                let mut seeds = [$vec::default(); 3];
-                rng.try_fill_bytes(seeds.as_byte_slice_mut())?;
+            fn from_seed(mut seed: Self::Seed) -> Self {
+                let seeds = read_seed!(seed, $vec; 3);
 
                 let seed_low = seeds[0];
-                let seed_high = seeds[0];
+                let seed_high = seeds[1];
 
                 let lcg_adder_low = (seed_low << 1) | 1;
                 let lcg_adder_high = (seed_low >> 63) | (seed_high << 1); //every bit of seed except the highest bit gets used in the adder
@@ -275,8 +341,10 @@ macro_rules! make_xsm64 {
 
                 xsm.lcg_high += seeds[2] << 31;
 
-                Ok(xsm)
+                xsm
             }
+
+            impl_seed_from_u64! {}
         }
     };
 }
@@ -284,7 +352,58 @@ macro_rules! make_xsm64 {
 // (where `l` is stream length)
 // (multiple parameters *might* be possible)
 // (jumping is possible)
-// Listing probability of overlap somewhere:                 Probability
-make_xsm64! { Xsm64x2, u64x2, u64x2 } // ≈ 2^2 * l / 2^128 ≈ l * 2^-126
-make_xsm64! { Xsm64x4, u64x4, u64x4 } // ≈ 4^2 * l / 2^128 ≈ l * 2^-124
-make_xsm64! { Xsm64x8, u64x8, u64x8 } // ≈ 8^2 * l / 2^128 ≈ l * 2^-122
+// Listing probability of overlap somewhere:                           Probability
+make_xsm64! { Xsm64x2, u64x2, u64x2, Seed384  } // ≈ 2^2 * l / 2^128 ≈ l * 2^-126
+make_xsm64! { Xsm64x4, u64x4, u64x4, Seed768  } // ≈ 4^2 * l / 2^128 ≈ l * 2^-124
+make_xsm64! { Xsm64x8, u64x8, u64x8, Seed1536 } // ≈ 8^2 * l / 2^128 ≈ l * 2^-122
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xsm32_seek() {
+        let start = Xsm32x4::seed_from_u64(0);
+        let mut rng = start.clone();
+        let mut seeked = start.clone();
+        for _ in 0..1000 {
+            rng.generate();
+        }
+        seeked.seek(1000);
+        assert_eq!(rng, seeked);
+
+        seeked.seek(1000_u64.wrapping_neg());
+        assert_eq!(seeked, start);
+
+        // each lane starts where the previous one would be after 2^62 outputs
+        let blocks = Xsm32x4::blocks_from_rng(SplitMix64::new(0)).unwrap();
+        let mut jumped = blocks.clone();
+        jumped.seek(1 << 62);
+        for i in 0..3 {
+            assert_eq!(jumped.lcg_low.extract(i), blocks.lcg_low.extract(i + 1));
+            assert_eq!(jumped.lcg_high.extract(i), blocks.lcg_high.extract(i + 1));
+        }
+    }
+
+    #[test]
+    fn xsm64_seek() {
+        let start = Xsm64x2::seed_from_u64(0);
+        let mut rng = start.clone();
+        let mut seeked = start.clone();
+        for _ in 0..1000 {
+            rng.generate();
+        }
+        seeked.seek(1000);
+        assert_eq!(rng, seeked);
+
+        seeked.seek(1000_u128.wrapping_neg());
+        assert_eq!(seeked, start);
+
+        // the second lane starts 2^127 outputs after the first
+        let blocks = Xsm64x2::blocks_from_rng(SplitMix64::new(0)).unwrap();
+        let mut jumped = blocks.clone();
+        jumped.seek(1 << 127);
+        assert_eq!(jumped.lcg_low.extract(0), blocks.lcg_low.extract(1));
+        assert_eq!(jumped.lcg_high.extract(0), blocks.lcg_high.extract(1));
+    }
+}
@@ -0,0 +1,336 @@
+//! Seed types for generators with more than 32 bytes of state, the
+//! SplitMix64 expansion behind `seed_from_u64`, and `SeedSequence` for
+//! hierarchical seeding.
+//!
+//! `SeedableRng::Seed` must implement `Default`, which arrays only do up to
+//! 32 elements. Seeds are read as little-endian lanes, vector by vector, in
+//! the order the fields are declared.
+
+use std::fmt;
+
+use rand::{Error, RngCore, SeedableRng};
+
+macro_rules! make_seed {
+    ($name:ident, $bytes:expr) => {
+        #[derive(Clone, Copy)]
+        pub struct $name(pub [u8; $bytes]);
+
+        impl Default for $name {
+            #[inline]
+            fn default() -> Self {
+                $name([0; $bytes])
+            }
+        }
+
+        impl AsMut<[u8]> for $name {
+            #[inline]
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.0
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            #[inline]
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0[..].fmt(f)
+            }
+        }
+    };
+}
+
+make_seed! { Seed384, 48 }
+make_seed! { Seed512, 64 }
+make_seed! { Seed640, 80 }
+make_seed! { Seed768, 96 }
+make_seed! { Seed1024, 128 }
+make_seed! { Seed1280, 160 }
+make_seed! { Seed1536, 192 }
+make_seed! { Seed2048, 256 }
+make_seed! { Seed2560, 320 }
+make_seed! { Seed4096, 512 }
+
+/// SplitMix64, used by `seed_from_u64` to expand a single `u64` into full
+/// vector state.
+///
+/// Every 64 bits of generator state is a separate output, so no two lanes
+/// share a state word. Going through `from_rng` and `from_seed` means each
+/// family's invalid states (all-zero xorshift lanes, LFSR lower bounds, ...)
+/// are fixed up as usual.
+///
+/// - Source: <http://xoshiro.di.unimi.it/splitmix64.c>
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(state: u64) -> Self {
+        Self { state }
+    }
+}
+
+impl RngCore for SplitMix64 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            let len = chunk.len();
+            chunk.copy_from_slice(&bytes[..len]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+const POOL_SIZE: usize = 4;
+const INIT_A: u32 = 0x43b0d7e5;
+const MULT_A: u32 = 0x931e8875;
+const INIT_B: u32 = 0x8b51f9dd;
+const MULT_B: u32 = 0x58f38ded;
+const MIX_MULT_L: u32 = 0xca01f9dd;
+const MIX_MULT_R: u32 = 0x4973f715;
+const XSHIFT: u32 = 16;
+
+#[inline]
+fn hashmix(value: u32, hash_const: &mut u32) -> u32 {
+    let mut value = value ^ *hash_const;
+    *hash_const = hash_const.wrapping_mul(MULT_A);
+    value = value.wrapping_mul(*hash_const);
+    value ^ (value >> XSHIFT)
+}
+
+#[inline]
+fn mix(x: u32, y: u32) -> u32 {
+    let result = MIX_MULT_L
+        .wrapping_mul(x)
+        .wrapping_sub(MIX_MULT_R.wrapping_mul(y));
+    result ^ (result >> XSHIFT)
+}
+
+/// A port of NumPy's `SeedSequence`.
+///
+/// Mixes user entropy and a spawn key into a 128-bit pool, from which seed
+/// material of any length is drawn. `spawn` derives child sequences by
+/// extending the spawn key, so a tree of generators can be seeded from a
+/// single root without chaining `from_rng` calls.
+///
+/// ```ignore
+/// let mut root = SeedSequence::from_u64(42);
+/// let rngs: Vec<Xoshiro256StarStarX4> =
+///     root.spawn(1000).iter().map(|s| s.seed_rng()).collect();
+/// ```
+///
+/// - Source: <https://github.com/numpy/numpy/blob/master/numpy/random/bit_generator.pyx>
+/// - Design: <http://www.pcg-random.org/posts/developing-a-seed_seq-alternative.html>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeedSequence {
+    entropy: Vec<u32>,
+    spawn_key: Vec<u32>,
+    pool: [u32; POOL_SIZE],
+    n_children_spawned: u32,
+}
+
+impl SeedSequence {
+    /// Entropy words are taken least significant first.
+    pub fn new(entropy: &[u32]) -> Self {
+        Self::with_spawn_key(entropy, &[])
+    }
+
+    /// Splits `entropy` into 32-bit words the way NumPy does for an integer
+    /// seed, so `from_u64(x)` mixes the same pool as `SeedSequence(x)`.
+    pub fn from_u64(entropy: u64) -> Self {
+        let low = entropy as u32;
+        let high = (entropy >> 32) as u32;
+        if high == 0 {
+            Self::new(&[low])
+        } else {
+            Self::new(&[low, high])
+        }
+    }
+
+    pub fn with_spawn_key(entropy: &[u32], spawn_key: &[u32]) -> Self {
+        let mut assembled = entropy.to_vec();
+        if !spawn_key.is_empty() && assembled.len() < POOL_SIZE {
+            // pad so a spawn key can't be mistaken for more entropy
+            assembled.resize(POOL_SIZE, 0);
+        }
+        assembled.extend_from_slice(spawn_key);
+
+        let mut pool = [0; POOL_SIZE];
+        let mut hash_const = INIT_A;
+        for (i, p) in pool.iter_mut().enumerate() {
+            *p = hashmix(assembled.get(i).cloned().unwrap_or(0), &mut hash_const);
+        }
+
+        // every pool word affects every other
+        for i_src in 0..POOL_SIZE {
+            for i_dst in 0..POOL_SIZE {
+                if i_src != i_dst {
+                    pool[i_dst] = mix(pool[i_dst], hashmix(pool[i_src], &mut hash_const));
+                }
+            }
+        }
+
+        // fold in any remaining entropy
+        for &word in assembled.iter().skip(POOL_SIZE) {
+            for p in pool.iter_mut() {
+                *p = mix(*p, hashmix(word, &mut hash_const));
+            }
+        }
+
+        Self {
+            entropy: entropy.to_vec(),
+            spawn_key: spawn_key.to_vec(),
+            pool,
+            n_children_spawned: 0,
+        }
+    }
+
+    pub fn entropy(&self) -> &[u32] {
+        &self.entropy
+    }
+
+    pub fn spawn_key(&self) -> &[u32] {
+        &self.spawn_key
+    }
+
+    /// Fills `dest` with seed words. Shorter requests are a prefix of longer
+    /// ones.
+    pub fn generate_state(&self, dest: &mut [u32]) {
+        let mut hash_const = INIT_B;
+        for (word, &p) in dest.iter_mut().zip(self.pool.iter().cycle()) {
+            let mut value = p ^ hash_const;
+            hash_const = hash_const.wrapping_mul(MULT_B);
+            value = value.wrapping_mul(hash_const);
+            *word = value ^ (value >> XSHIFT);
+        }
+    }
+
+    /// The words of `generate_state`, little-endian.
+    pub fn fill_bytes(&self, dest: &mut [u8]) {
+        let mut words = vec![0; (dest.len() + 3) / 4];
+        self.generate_state(&mut words);
+        for (chunk, word) in dest.chunks_mut(4).zip(&words) {
+            let bytes = word.to_le_bytes();
+            let len = chunk.len();
+            chunk.copy_from_slice(&bytes[..len]);
+        }
+    }
+
+    /// Seeds a generator with `SeedableRng::from_seed`.
+    pub fn seed_rng<R: SeedableRng>(&self) -> R {
+        let mut seed = R::Seed::default();
+        self.fill_bytes(seed.as_mut());
+        R::from_seed(seed)
+    }
+
+    /// Derives `n` child sequences. Later calls continue numbering where the
+    /// last left off, so children are never repeated.
+    ///
+    /// Panics past 2^32 - 1 children in total, rather than wrap around to
+    /// children already handed out.
+    pub fn spawn(&mut self, n: u32) -> Vec<SeedSequence> {
+        let start = self.n_children_spawned;
+        self.n_children_spawned = start
+            .checked_add(n)
+            .expect("spawned more than 2^32 - 1 children of one SeedSequence");
+
+        (start..self.n_children_spawned)
+            .map(|i| {
+                let mut spawn_key = self.spawn_key.clone();
+                spawn_key.push(i);
+                Self::with_spawn_key(&self.entropy, &spawn_key)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SeedSequence, SplitMix64};
+    use rand::RngCore;
+
+    #[test]
+    fn splitmix64_reference() {
+        let mut rng = SplitMix64::new(0);
+        assert_eq!(rng.next_u64(), 0xe220a8397b1dcdaf);
+        assert_eq!(rng.next_u64(), 0x6e789e6aa1b965f4);
+        assert_eq!(rng.next_u64(), 0x06c45d188009454f);
+    }
+
+    #[test]
+    fn seed_sequence_spawn() {
+        let mut root = SeedSequence::from_u64(42);
+        let children = root.spawn(2);
+        let later = root.spawn(1);
+
+        assert_eq!(children[1].spawn_key(), &[1]);
+        assert_eq!(later[0].spawn_key(), &[2]);
+        assert_eq!(children[0], SeedSequence::with_spawn_key(&[42], &[0]));
+
+        let mut a = [0; 8];
+        let mut b = [0; 8];
+        children[0].generate_state(&mut a);
+        children[1].generate_state(&mut b);
+        assert_ne!(a, b);
+
+        // shorter requests are a prefix of longer ones
+        let mut c = [0; 3];
+        children[0].generate_state(&mut c);
+        assert_eq!(c, a[..3]);
+    }
+
+    // NumPy's `test_seed_sequence.py::test_reference_data`, then
+    // `SeedSequence(42).spawn(2)[1]` and `SeedSequence(0x123456789abcdef0)`
+    // spawned down to key `(0, 3)`
+    #[test]
+    fn seed_sequence_matches_numpy() {
+        let state = |seq: &SeedSequence| {
+            let mut words = [0; 4];
+            seq.generate_state(&mut words);
+            words
+        };
+
+        let seq = SeedSequence::new(&[3735928559, 195939070, 229505742, 305419896]);
+        assert_eq!(state(&seq), [3914649087, 576849849, 3593928901, 2229911004]);
+
+        let mut root = SeedSequence::from_u64(42);
+        assert_eq!(state(&root), [3444837047, 2669555309, 2046530742, 3581440988]);
+        assert_eq!(state(&root.spawn(2)[1]), [4091952314, 31242083, 366899054, 1794014678]);
+
+        let mut root = SeedSequence::from_u64(0x1234_5678_9abc_def0);
+        let child = root.spawn(1).remove(0).spawn(4).remove(3);
+        assert_eq!(child.spawn_key(), &[0, 3]);
+        assert_eq!(state(&child), [3973681195, 2790715690, 2126447028, 2790938848]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn spawn_count_overflow() {
+        let mut root = SeedSequence::from_u64(0);
+        root.n_children_spawned = u32::max_value();
+        root.spawn(1);
+    }
+}
@@ -0,0 +1,220 @@
+//! `serde` support, behind the `serde1` feature.
+//!
+//! Generator state is serialized field by field. Vectors are written lane by
+//! lane, lane 0 first, as their scalar type, so the layout is the same on
+//! every host regardless of endianness. `__m128i` is written as a `u64x2`.
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+use std::fmt;
+use std::marker::PhantomData;
+
+use packed_simd::*;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+/// A field of generator state.
+pub trait Lanes: Sized {
+    fn serialize_lanes<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+    fn deserialize_lanes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+/// Serializes a borrowed field with `Lanes`.
+pub struct Borrowed<'a, T: 'a>(pub &'a T);
+
+impl<'a, T: Lanes> Serialize for Borrowed<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_lanes(serializer)
+    }
+}
+
+/// Deserializes an owned field with `Lanes`.
+pub struct Owned<T>(pub T);
+
+impl<'de, T: Lanes> Deserialize<'de> for Owned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_lanes(deserializer).map(Owned)
+    }
+}
+
+macro_rules! impl_lanes_vector {
+    ($($vector:ident: $scalar:ty,)+) => {
+        $(
+            impl Lanes for $vector {
+                fn serialize_lanes<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let mut tuple = serializer.serialize_tuple($vector::lanes())?;
+                    for i in 0..$vector::lanes() {
+                        tuple.serialize_element(&self.extract(i))?;
+                    }
+                    tuple.end()
+                }
+
+                fn deserialize_lanes<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    struct LanesVisitor;
+
+                    impl<'de> Visitor<'de> for LanesVisitor {
+                        type Value = $vector;
+
+                        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                            write!(f, "{} lanes of {}", $vector::lanes(), stringify!($scalar))
+                        }
+
+                        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<$vector, A::Error> {
+                            let mut vector = $vector::default();
+                            for i in 0..$vector::lanes() {
+                                let lane: $scalar = seq
+                                    .next_element()?
+                                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                                vector = vector.replace(i, lane);
+                            }
+                            Ok(vector)
+                        }
+                    }
+
+                    deserializer.deserialize_tuple($vector::lanes(), LanesVisitor)
+                }
+            }
+        )+
+    };
+}
+
+impl_lanes_vector! {
+    u8x2: u8, u8x4: u8, u8x8: u8, u8x16: u8, u8x32: u8, u8x64: u8,
+    u16x2: u16, u16x4: u16, u16x8: u16, u16x16: u16, u16x32: u16,
+    u32x2: u32, u32x4: u32, u32x8: u32, u32x16: u32,
+    u64x2: u64, u64x4: u64, u64x8: u64,
+}
+
+macro_rules! impl_lanes_array {
+    ($($n:expr,)+) => {
+        $(
+            impl<T: Lanes + Copy> Lanes for [T; $n] {
+                fn serialize_lanes<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let mut tuple = serializer.serialize_tuple($n)?;
+                    for x in self.iter() {
+                        tuple.serialize_element(&Borrowed(x))?;
+                    }
+                    tuple.end()
+                }
+
+                fn deserialize_lanes<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    struct ArrayVisitor<T>(PhantomData<T>);
+
+                    impl<'de, T: Lanes + Copy> Visitor<'de> for ArrayVisitor<T> {
+                        type Value = [T; $n];
+
+                        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                            write!(f, "an array of length {}", $n)
+                        }
+
+                        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<[T; $n], A::Error> {
+                            let mut next = |i| -> Result<T, A::Error> {
+                                let x: Owned<T> = seq
+                                    .next_element()?
+                                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                                Ok(x.0)
+                            };
+
+                            // not every element type implements `Default`
+                            let mut array = [next(0)?; $n];
+                            for (i, x) in array.iter_mut().enumerate().skip(1) {
+                                *x = next(i)?;
+                            }
+                            Ok(array)
+                        }
+                    }
+
+                    deserializer.deserialize_tuple($n, ArrayVisitor(PhantomData))
+                }
+            }
+        )+
+    };
+}
+
+impl_lanes_array! { 2, 3, 4, 8, 11, 16, }
+
+macro_rules! impl_lanes_scalar {
+    ($($ty:ty,)+) => {
+        $(
+            impl Lanes for $ty {
+                fn serialize_lanes<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.serialize(serializer)
+                }
+
+                fn deserialize_lanes<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Self, D::Error> {
+                    Self::deserialize(deserializer)
+                }
+            }
+        )+
+    };
+}
+
+impl_lanes_scalar! { bool, u8, }
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+impl Lanes for __m128i {
+    fn serialize_lanes<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        u64x2::from_bits(*self).serialize_lanes(serializer)
+    }
+
+    fn deserialize_lanes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64x2::deserialize_lanes(deserializer).map(__m128i::from_bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngCore, SeedableRng};
+    use serde_json::{self, Value};
+    use {Aes128Ctr, ChaCha8x8, Mwc8, SimdRng, Xoshiro256StarStarX4};
+
+    #[test]
+    fn resumes_mid_buffer() {
+        let mut rng = Aes128Ctr::seed_from_u64(0);
+        for _ in 0..3 {
+            rng.generate();
+        }
+        let json = serde_json::to_string(&rng).unwrap();
+        let mut resumed: Aes128Ctr = serde_json::from_str(&json).unwrap();
+        assert_eq!(resumed, rng);
+        for _ in 0..20 {
+            assert_eq!(resumed.generate(), rng.generate());
+        }
+
+        let mut rng = ChaCha8x8::seed_from_u64(0);
+        rng.next_u32();
+        let value = serde_json::to_value(&rng).unwrap();
+        let mut resumed: ChaCha8x8 = serde_json::from_value(value).unwrap();
+        for _ in 0..20 {
+            assert_eq!(resumed.generate(), rng.generate());
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_states() {
+        let valid = serde_json::to_value(&Mwc8::seed_from_u64(0)).unwrap();
+
+        let mut idx = valid.clone();
+        idx["idx"] = Value::from(4);
+        assert!(serde_json::from_value::<Mwc8>(idx).is_err());
+
+        let mut carry = valid.clone();
+        carry["buffer"][0][0] = Value::from(u64::max_value());
+        assert!(serde_json::from_value::<Mwc8>(carry).is_err());
+        assert!(serde_json::from_value::<Mwc8>(valid).is_ok());
+
+        let mut zero = serde_json::to_value(&Xoshiro256StarStarX4::seed_from_u64(0)).unwrap();
+        for field in &["s0", "s1", "s2", "s3"] {
+            zero[*field][2] = Value::from(0);
+        }
+        assert!(serde_json::from_value::<Xoshiro256StarStarX4>(zero).is_err());
+    }
+}
@@ -0,0 +1,36 @@
+//! Fork-join friendly splitting.
+
+use rand::{RngCore, SeedableRng};
+
+use seed::SeedSequence;
+
+/// A generator that can fork off a child with a stream independent of its
+/// own.
+///
+/// Splitting is a pure function of the parent's state: the same parent
+/// always yields the same child and the same new parent, so recursively
+/// forked work draws the same numbers however its tasks are scheduled.
+///
+/// - Counter-based generators derive the child's key from the parent's next
+///   block.
+/// - Jumpable generators seed the child from the parent's next outputs,
+///   see `reseed`. Jumping alone would hand a child and its parent's next
+///   child the same segment once the child splits again.
+///
+/// Either way the parent moves past what it handed out, so children are
+/// distinct at every depth of the tree.
+pub trait SplittableRng: Sized {
+    /// Returns a child and moves `self` past it.
+    fn split(&mut self) -> Self;
+}
+
+/// Seeds a child from 128 bits of `parent`'s output, mixed through
+/// `SeedSequence` so the child's state isn't a linear function of the
+/// parent's.
+pub(crate) fn reseed<R: RngCore + SeedableRng>(parent: &mut R) -> R {
+    let mut entropy = [0; 4];
+    for word in entropy.iter_mut() {
+        *word = parent.next_u32();
+    }
+    SeedSequence::new(&entropy).seed_rng()
+}
@@ -65,18 +65,231 @@ macro_rules! impl_rngcore {
     };
 }
 
+/// `SeedableRng::seed_from_u64` through `SplitMix64`, invoked inside each
+/// `SeedableRng` impl. See `SplitMix64` for why.
+macro_rules! impl_seed_from_u64 {
+    () => {
+        fn seed_from_u64(state: u64) -> Self {
+            Self::from_rng(SplitMix64::new(state)).unwrap()
+        }
+    };
+}
+
+/// Reads `$n` vectors of little-endian lanes from a `SeedableRng::Seed`.
+macro_rules! read_seed {
+    ($seed:expr, $vector:ident; $n:expr) => {{
+        let mut vectors = [$vector::default(); $n];
+        vectors.as_byte_slice_mut().copy_from_slice($seed.as_mut());
+        for v in vectors.iter_mut() {
+            *v = $vector::from_le(*v);
+        }
+        vectors
+    }};
+}
+
+/// Replacements for invalid seed lanes: a different SplitMix64 output per
+/// lane, so lanes fixed up together don't become identical streams. `$key`
+/// picks the SplitMix64 stream, for separate state words.
+macro_rules! lane_fill {
+    ($vector:ident, $key:expr) => {{
+        let mut rng = SplitMix64::new($key);
+        let mut fill = $vector::splat(0);
+        for lane in 0..$vector::lanes() {
+            fill = fill.replace(lane, rng.next_u64() as _);
+        }
+        fill
+    }};
+}
+
+/// Jump-ahead for generators stepping `state = state * $mul + inc`.
+///
+/// - Source: Brown, "Random Number Generation with Arbitrary Stride" (1994)
+macro_rules! impl_lcg_advance {
+    ($rng_name:ident, $vector:ident, $mul:expr) => {
+        impl $rng_name {
+            /// Advances every lane by `delta` steps in O(log delta), as if
+            /// `generate` had been called `delta` times.
+            pub fn advance(&mut self, delta: u64) {
+                // truncating is exact, the period divides 2^bits
+                self.advance_lanes($vector::splat(delta as _));
+            }
+
+            /// Advances each lane by its own number of steps.
+            pub fn advance_lanes(&mut self, mut delta: $vector) {
+                let zero = $vector::splat(0);
+                let one = $vector::splat(1);
+
+                let mut acc_mult = one;
+                let mut acc_plus = zero;
+                let mut cur_mult = $vector::splat($mul);
+                let mut cur_plus = self.inc;
+                while delta.ne(zero).any() {
+                    let odd = (delta & one).eq(one);
+                    acc_mult = odd.select(acc_mult * cur_mult, acc_mult);
+                    acc_plus = odd.select(acc_plus * cur_mult + cur_plus, acc_plus);
+                    cur_plus = (cur_mult + one) * cur_plus;
+                    cur_mult *= cur_mult;
+                    delta >>= 1;
+                }
+                self.state = acc_mult * self.state + acc_plus;
+            }
+        }
+    };
+}
+
+/// Implements `Debug` over the listed state fields, with vector lanes in hex.
+macro_rules! impl_debug {
+    ($rng:ident { $($field:ident),+ }) => {
+        impl ::std::fmt::Debug for $rng {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                use $crate::debug::Hex;
+
+                f.debug_struct(stringify!($rng))
+                    $(.field(stringify!($field), &Hex(&self.$field)))+
+                    .finish()
+            }
+        }
+    };
+}
+
+/// Implements `Serialize` and `Deserialize` over the listed state fields.
+///
+/// A `where` check rejects deserialized states the generator can't step from,
+/// such as out of range buffer indices or all-zero lanes.
+#[cfg(feature = "serde1")]
+macro_rules! impl_serde {
+    ($rng:ident { $($field:ident),+ }) => {
+        impl_serde! { $rng { $($field),+ } where |_| Ok(()) }
+    };
+    ($rng:ident { $($field:ident),+ } where $check:expr) => {
+        impl ::serde::Serialize for $rng {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                use ::serde::ser::SerializeStruct;
+                use $crate::serde_impls::Borrowed;
+
+                let fields = [$(stringify!($field)),+];
+                let mut state = serializer.serialize_struct(stringify!($rng), fields.len())?;
+                $(
+                    state.serialize_field(stringify!($field), &Borrowed(&self.$field))?;
+                )+
+                state.end()
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $rng {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                use ::serde::de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor};
+                use std::fmt;
+                use $crate::serde_impls::Owned;
+
+                const FIELDS: &[&str] = &[$(stringify!($field)),+];
+
+                fn validate<E: de::Error>(rng: $rng) -> Result<$rng, E> {
+                    let check: fn(&$rng) -> Result<(), &'static str> = $check;
+                    match check(&rng) {
+                        Ok(()) => Ok(rng),
+                        Err(invalid) => Err(E::invalid_value(
+                            de::Unexpected::Other(invalid),
+                            &"a valid generator state",
+                        )),
+                    }
+                }
+
+                struct StateVisitor;
+
+                impl<'de> Visitor<'de> for StateVisitor {
+                    type Value = $rng;
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        write!(f, "struct {}", stringify!($rng))
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<$rng, A::Error>
+                    where
+                        A: SeqAccess<'de>,
+                    {
+                        let mut i = 0;
+                        $(
+                            let $field = match seq.next_element::<Owned<_>>()? {
+                                Some(x) => x.0,
+                                None => return Err(de::Error::invalid_length(i, &self)),
+                            };
+                            i += 1;
+                        )+
+                        let _ = i;
+                        validate($rng { $($field),+ })
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<$rng, A::Error>
+                    where
+                        A: MapAccess<'de>,
+                    {
+                        $(
+                            let mut $field = None;
+                        )+
+                        while let Some(key) = map.next_key::<String>()? {
+                            $(
+                                if key == stringify!($field) {
+                                    if $field.is_some() {
+                                        return Err(de::Error::duplicate_field(stringify!($field)));
+                                    }
+                                    $field = Some(map.next_value::<Owned<_>>()?.0);
+                                    continue;
+                                }
+                            )+
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                        $(
+                            let $field = match $field {
+                                Some(x) => x,
+                                None => return Err(de::Error::missing_field(stringify!($field))),
+                            };
+                        )+
+                        validate($rng { $($field),+ })
+                    }
+                }
+
+                deserializer.deserialize_struct(stringify!($rng), FIELDS, StateVisitor)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde1"))]
+macro_rules! impl_serde {
+    ($rng:ident { $($field:ident),+ }) => {};
+    ($rng:ident { $($field:ident),+ } where $check:expr) => {};
+}
+
 // exported for use in external benchmarks
 #[doc(hidden)]
 #[macro_export]
 macro_rules! for_each_prng {
     ($macro:ident) => {
+        $macro! { Aes128Ctr, u32x4, f32x4 }
+
         $macro! { AesRand, u32x4, f32x4 }
+        $macro! { AesRandX2, u32x4, f32x4 }
+        $macro! { AesRandX4, u32x4, f32x4 }
+        $macro! { AesRandX8, u32x4, f32x4 }
 
         $macro! { Ars5, u32x4, f32x4 }
         $macro! { Ars7, u32x4, f32x4 }
 
         $macro! { ChaCha4, u32x4, f32x4 }
         $macro! { ChaChaAlt4, u32x4, f32x4 }
+        $macro! { ChaCha8x8, u32x16, f32x16 }
+        $macro! { ChaCha8x16, u32x16, f32x16 }
+        $macro! { ChaCha12x8, u32x16, f32x16 }
+        $macro! { ChaCha12x16, u32x16, f32x16 }
+        $macro! { ChaCha20x8, u32x16, f32x16 }
+        $macro! { ChaCha20x16, u32x16, f32x16 }
 
         $macro! { IntelLcg, u32x4, f32x4 }
 
@@ -107,9 +320,17 @@ macro_rules! for_each_prng {
         $macro! { Lfsr258x4, u32x4, f32x4 }
         $macro! { Lfsr258x8, u32x8, f32x8 }
 
+        $macro! { Mrg32k3aX2, u32x2, f32x2 }
+        $macro! { Mrg32k3aX4, u32x4, f32x4 }
+        $macro! { Mrg32k3aX8, u32x8, f32x8 }
+
+        $macro! { Mrg8, u32x8, f32x8 }
+
         $macro! { Mwc2, u32x2, f32x2 }
         $macro! { Mwc4, u32x4, f32x4 }
         $macro! { Mwc8, u32x8, f32x8 }
+        $macro! { MwcX4, u64x4, f32x8 }
+        $macro! { MwcX8, u64x8, f32x16 }
 
         $macro! { Pcg32x2, u32x2, f32x2 }
         $macro! { Pcg32x4, u32x4, f32x4 }
@@ -123,6 +344,22 @@ macro_rules! for_each_prng {
         $macro! { PcgFixedXsl32x4, u32x4, f32x4 }
         $macro! { PcgFixedXsl32x8, u32x8, f32x8 }
 
+        $macro! { Philox2x64x2, u64x2, f32x4 }
+        $macro! { Philox2x64x4, u64x4, f32x8 }
+        $macro! { Philox2x64x8, u64x8, f32x16 }
+
+        $macro! { Philox4x32x4, u32x4, f32x4 }
+        $macro! { Philox4x32x8, u32x8, f32x8 }
+        $macro! { Philox4x32x16, u32x16, f32x16 }
+
+        $macro! { Philox4x32R7x4, u32x4, f32x4 }
+        $macro! { Philox4x32R7x8, u32x8, f32x8 }
+        $macro! { Philox4x32R7x16, u32x16, f32x16 }
+
+        $macro! { Salsa8, u32x16, f32x16 }
+        $macro! { Salsa12, u32x16, f32x16 }
+        $macro! { Salsa20, u32x16, f32x16 }
+
         $macro! { Sfc16x2, u16x2, f32x2 } // too small for SIMD floats
         $macro! { Sfc16x4, u16x4, f32x2 }
         $macro! { Sfc16x8, u16x8, f32x4 }
@@ -138,6 +375,22 @@ macro_rules! for_each_prng {
         $macro! { Sfc64x4, u32x4, f32x4 }
         $macro! { Sfc64x8, u32x8, f32x8 }
 
+        $macro! { Threefry2x64R13x2, u64x2, f32x4 }
+        $macro! { Threefry2x64R13x4, u64x4, f32x8 }
+        $macro! { Threefry2x64R13x8, u64x8, f32x16 }
+
+        $macro! { Threefry2x64R20x2, u64x2, f32x4 }
+        $macro! { Threefry2x64R20x4, u64x4, f32x8 }
+        $macro! { Threefry2x64R20x8, u64x8, f32x16 }
+
+        $macro! { Threefry4x64R13x2, u64x2, f32x4 }
+        $macro! { Threefry4x64R13x4, u64x4, f32x8 }
+        $macro! { Threefry4x64R13x8, u64x8, f32x16 }
+
+        $macro! { Threefry4x64R20x2, u64x2, f32x4 }
+        $macro! { Threefry4x64R20x4, u64x4, f32x8 }
+        $macro! { Threefry4x64R20x8, u64x8, f32x16 }
+
         $macro! { Xoroshiro128StarStarX2, u64x2, f32x4 }
         $macro! { Xoroshiro128StarStarX4, u64x4, f32x8 }
         $macro! { Xoroshiro128StarStarX8, u64x8, f32x16 }